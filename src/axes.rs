@@ -0,0 +1,485 @@
+use egui::{pos2, vec2, Color32, Pos2, Vec2};
+
+use crate::{
+    builder::Builder,
+    component::{Component, ComponentExt, Handle},
+    dynamics::Dynamic,
+    group::Group,
+    object::{FillMaterial, Material, ObjectId, StrokeMaterial, Transform},
+    shapes::Line,
+    typst::{CompiledTypst, Typst},
+    world::World,
+};
+
+/// A range of values along an axis, with a tick every `step`.
+#[derive(Clone, Copy, Debug)]
+pub struct AxisRange {
+    pub min: f32,
+    pub max: f32,
+    pub step: f32,
+}
+
+impl AxisRange {
+    /// Panics unless `min` is less than `max` and `step` is positive.
+    pub fn new(min: f32, max: f32, step: f32) -> Self {
+        assert!(min < max, "axis range {min}..{max} is empty");
+        assert!(step > 0.0, "axis step must be positive, got {step}");
+        Self { min, max, step }
+    }
+
+    /// Every multiple of the step within the range, or none if the step isn't
+    /// positive.
+    pub fn ticks(&self) -> Vec<f32> {
+        if !(self.step > 0.0 && self.step.is_finite()) {
+            return Vec::new();
+        }
+
+        let first = (self.min / self.step).ceil() as i32;
+        let last = (self.max / self.step).floor() as i32;
+
+        (first..=last).map(|i| i as f32 * self.step).collect()
+    }
+
+    /// Where the other axis crosses this one: at zero if it is in range,
+    /// otherwise at the closest end.
+    fn origin(&self) -> f32 {
+        0.0_f32.clamp(self.min, self.max)
+    }
+
+    fn alpha(&self, value: f32) -> f32 {
+        let width = self.max - self.min;
+        if width == 0.0 {
+            // Everything is in the middle of an axis without a range.
+            return 0.5;
+        }
+
+        (value - self.min) / width
+    }
+
+    fn value(&self, alpha: f32) -> f32 {
+        self.min + alpha * (self.max - self.min)
+    }
+}
+
+/// Maps between data coordinates and the coordinates of a pair of axes. Data
+/// coordinates are y-up, while scene coordinates are y-down.
+#[derive(Clone, Copy, Debug)]
+pub struct CoordinateSystem {
+    pub x_range: AxisRange,
+    pub y_range: AxisRange,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl CoordinateSystem {
+    pub fn coords_to_point(&self, x: f32, y: f32) -> Pos2 {
+        pos2(
+            (self.x_range.alpha(x) - 0.5) * self.width,
+            (0.5 - self.y_range.alpha(y)) * self.height,
+        )
+    }
+
+    pub fn point_to_coords(&self, point: Pos2) -> (f32, f32) {
+        (
+            self.x_range.value(point.x / self.width + 0.5),
+            self.y_range.value(0.5 - point.y / self.height),
+        )
+    }
+
+    fn origin(&self) -> Pos2 {
+        self.coords_to_point(self.x_range.origin(), self.y_range.origin())
+    }
+}
+
+/// A point in the data space of a pair of axes, resolved against wherever the
+/// axes currently are in the world.
+#[derive(Clone)]
+pub struct AxesPoint {
+    /// An object sharing the axes' coordinate space.
+    frame: ObjectId,
+    point: Pos2,
+}
+
+impl Dynamic<Pos2> for AxesPoint {
+    fn get(&self, world: &World) -> Pos2 {
+        world.objects.world_transform(self.frame).apply(self.point)
+    }
+}
+
+fn format_tick(value: f32, step: f32) -> String {
+    // Enough decimals to tell every tick apart.
+    let decimals = (0..6)
+        .find(|&decimals| {
+            let scaled = step * 10.0_f32.powi(decimals);
+            (scaled - scaled.round()).abs() < 1e-3
+        })
+        .unwrap_or(6) as usize;
+
+    format!("${:.*}$", decimals, value)
+}
+
+/// A pair of x and y axes with ticks and Typst-rendered labels.
+pub struct Axes {
+    system: CoordinateSystem,
+    material: StrokeMaterial,
+    tick_size: f32,
+    tick_labels: bool,
+    label_material: Material,
+    label_scale: f32,
+    x_label: Option<String>,
+    y_label: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct AxesHandle {
+    pub system: CoordinateSystem,
+    pub x_axis: Handle<Line>,
+    pub y_axis: Handle<Line>,
+    pub ticks: Handle<Group<Line>>,
+    pub tick_labels: Vec<Handle<Typst>>,
+    pub x_label: Option<Handle<Typst>>,
+    pub y_label: Option<Handle<Typst>>,
+}
+
+impl Axes {
+    /// Axes spanning the ranges, one scene unit per data unit by default.
+    pub fn new(x_range: AxisRange, y_range: AxisRange) -> Self {
+        Self {
+            system: CoordinateSystem {
+                x_range,
+                y_range,
+                width: x_range.max - x_range.min,
+                height: y_range.max - y_range.min,
+            },
+            material: StrokeMaterial::new(Color32::WHITE, 0.03),
+            tick_size: 0.2,
+            tick_labels: true,
+            label_material: FillMaterial::new(Color32::WHITE).into(),
+            label_scale: 0.6,
+            x_label: None,
+            y_label: None,
+        }
+    }
+
+    /// The size of the axes in scene units.
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.system.width = width;
+        self.system.height = height;
+        self
+    }
+
+    pub fn with_material(mut self, material: StrokeMaterial) -> Self {
+        self.material = material;
+        self
+    }
+
+    pub fn with_tick_size(mut self, tick_size: f32) -> Self {
+        self.tick_size = tick_size;
+        self
+    }
+
+    /// The material and scale of both the tick and axis labels.
+    pub fn with_labels(mut self, material: impl Into<Material>, scale: f32) -> Self {
+        self.label_material = material.into();
+        self.label_scale = scale;
+        self
+    }
+
+    pub fn with_tick_labels(mut self, tick_labels: bool) -> Self {
+        self.tick_labels = tick_labels;
+        self
+    }
+
+    /// Typst markup shown past the end of the x axis.
+    pub fn with_x_label(mut self, label: impl Into<String>) -> Self {
+        self.x_label = Some(label.into());
+        self
+    }
+
+    /// Typst markup shown past the top of the y axis.
+    pub fn with_y_label(mut self, label: impl Into<String>) -> Self {
+        self.y_label = Some(label.into());
+        self
+    }
+
+    /// Adds a label centered at `position`, moved by `offset` times its own
+    /// size so it can be placed next to a point without overlapping it.
+    fn add_label<B: Builder>(
+        &self,
+        builder: &mut B,
+        text: String,
+        position: Pos2,
+        offset: Vec2,
+    ) -> Handle<Typst> {
+        let glyphs = Typst::compile(&text);
+        let size = Typst::bounds(&glyphs).size() * self.label_scale;
+
+        let handle = builder.add(
+            CompiledTypst {
+                glyphs,
+                material: self.label_material.clone(),
+            }
+            .with_transform(
+                Transform::default()
+                    .with_position(position + offset * size)
+                    .with_scale(self.label_scale),
+            ),
+        );
+
+        Handle {
+            inner: handle.inner,
+            object_id: handle.object_id,
+        }
+    }
+}
+
+impl Component for Axes {
+    type Handle = AxesHandle;
+
+    fn build<B: Builder>(self, builder: &mut B) -> Self::Handle {
+        let system = self.system;
+        let origin = system.origin();
+        let (x_range, y_range) = (system.x_range, system.y_range);
+
+        let x_axis = builder.add(Line {
+            start: system.coords_to_point(x_range.min, y_range.origin()),
+            end: system.coords_to_point(x_range.max, y_range.origin()),
+            material: self.material.clone().into(),
+        });
+        let y_axis = builder.add(Line {
+            start: system.coords_to_point(x_range.origin(), y_range.min),
+            end: system.coords_to_point(x_range.origin(), y_range.max),
+            material: self.material.clone().into(),
+        });
+
+        let half_tick = self.tick_size / 2.0;
+        let mut ticks = Group::new();
+        let mut tick_labels = Vec::new();
+
+        for x in x_range.ticks() {
+            if x == x_range.origin() {
+                continue;
+            }
+
+            let point = pos2(system.coords_to_point(x, 0.0).x, origin.y);
+            ticks.add(Line {
+                start: point - vec2(0.0, half_tick),
+                end: point + vec2(0.0, half_tick),
+                material: self.material.clone().into(),
+            });
+
+            if self.tick_labels {
+                let position = point + vec2(0.0, self.tick_size);
+                let label = format_tick(x, x_range.step);
+                tick_labels.push(self.add_label(builder, label, position, vec2(0.0, 0.5)));
+            }
+        }
+
+        for y in y_range.ticks() {
+            if y == y_range.origin() {
+                continue;
+            }
+
+            let point = pos2(origin.x, system.coords_to_point(0.0, y).y);
+            ticks.add(Line {
+                start: point - vec2(half_tick, 0.0),
+                end: point + vec2(half_tick, 0.0),
+                material: self.material.clone().into(),
+            });
+
+            if self.tick_labels {
+                let position = point - vec2(self.tick_size, 0.0);
+                let label = format_tick(y, y_range.step);
+                tick_labels.push(self.add_label(builder, label, position, vec2(-0.5, 0.0)));
+            }
+        }
+
+        let ticks = builder.add(ticks);
+
+        let x_label = self.x_label.clone().map(|label| {
+            let end = x_axis.end + vec2(self.tick_size, 0.0);
+            self.add_label(builder, label, end, vec2(0.5, 0.0))
+        });
+        let y_label = self.y_label.clone().map(|label| {
+            let end = y_axis.end - vec2(0.0, self.tick_size);
+            self.add_label(builder, label, end, vec2(0.0, -0.5))
+        });
+
+        AxesHandle {
+            system,
+            x_axis,
+            y_axis,
+            ticks,
+            tick_labels,
+            x_label,
+            y_label,
+        }
+    }
+}
+
+impl AxesHandle {
+    /// Maps data coordinates to a point in the axes' coordinate space.
+    pub fn coords_to_point(&self, x: f32, y: f32) -> Pos2 {
+        self.system.coords_to_point(x, y)
+    }
+
+    /// Maps a point in the axes' coordinate space to data coordinates.
+    pub fn point_to_coords(&self, point: Pos2) -> (f32, f32) {
+        self.system.point_to_coords(point)
+    }
+
+    /// A point in data coordinates that follows the axes as they move.
+    pub fn coords(&self, x: f32, y: f32) -> AxesPoint {
+        AxesPoint {
+            frame: self.x_axis.object_id,
+            point: self.coords_to_point(x, y),
+        }
+    }
+}
+
+/// Axes on top of a background grid with a line at every tick.
+pub struct NumberPlane {
+    axes: Axes,
+    grid_material: StrokeMaterial,
+}
+
+#[derive(Clone)]
+pub struct NumberPlaneHandle {
+    pub axes: Handle<Axes>,
+    pub vertical_lines: Handle<Group<Line>>,
+    pub horizontal_lines: Handle<Group<Line>>,
+}
+
+impl NumberPlane {
+    pub fn new(x_range: AxisRange, y_range: AxisRange) -> Self {
+        Self {
+            axes: Axes::new(x_range, y_range).with_tick_labels(false),
+            grid_material: StrokeMaterial::new(Color32::from_rgb(40, 80, 120), 0.02),
+        }
+    }
+
+    /// Changes the axes drawn on top of the grid, e.g., to add labels.
+    pub fn with_axes(mut self, axes: impl FnOnce(Axes) -> Axes) -> Self {
+        self.axes = axes(self.axes);
+        self
+    }
+
+    pub fn with_grid_material(mut self, material: StrokeMaterial) -> Self {
+        self.grid_material = material;
+        self
+    }
+}
+
+impl Component for NumberPlane {
+    type Handle = NumberPlaneHandle;
+
+    fn build<B: Builder>(self, builder: &mut B) -> Self::Handle {
+        let system = self.axes.system;
+        let (x_range, y_range) = (system.x_range, system.y_range);
+
+        let mut vertical_lines = Group::new();
+        for x in x_range.ticks() {
+            vertical_lines.add(Line {
+                start: system.coords_to_point(x, y_range.min),
+                end: system.coords_to_point(x, y_range.max),
+                material: self.grid_material.clone().into(),
+            });
+        }
+
+        let mut horizontal_lines = Group::new();
+        for y in y_range.ticks() {
+            horizontal_lines.add(Line {
+                start: system.coords_to_point(x_range.min, y),
+                end: system.coords_to_point(x_range.max, y),
+                material: self.grid_material.clone().into(),
+            });
+        }
+
+        NumberPlaneHandle {
+            vertical_lines: builder.add(vertical_lines),
+            horizontal_lines: builder.add(horizontal_lines),
+            axes: builder.add(self.axes),
+        }
+    }
+}
+
+impl NumberPlaneHandle {
+    pub fn coords_to_point(&self, x: f32, y: f32) -> Pos2 {
+        self.axes.coords_to_point(x, y)
+    }
+
+    pub fn point_to_coords(&self, point: Pos2) -> (f32, f32) {
+        self.axes.point_to_coords(point)
+    }
+
+    pub fn coords(&self, x: f32, y: f32) -> AxesPoint {
+        self.axes.coords(x, y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ticks_are_the_multiples_of_the_step_in_range() {
+        assert_eq!(
+            AxisRange::new(-2.0, 3.0, 1.0).ticks(),
+            [-2.0, -1.0, 0.0, 1.0, 2.0, 3.0]
+        );
+        assert_eq!(
+            AxisRange::new(-1.2, 1.7, 0.5).ticks(),
+            [-1.0, -0.5, 0.0, 0.5, 1.0, 1.5]
+        );
+        assert_eq!(AxisRange::new(0.5, 0.9, 1.0).ticks(), Vec::<f32>::new());
+    }
+
+    #[test]
+    fn ticks_without_a_positive_step_are_empty() {
+        for step in [0.0, -1.0, f32::NAN, f32::INFINITY] {
+            let range = AxisRange {
+                min: -1.0,
+                max: 1.0,
+                step,
+            };
+            assert!(range.ticks().is_empty(), "step {step}");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "axis step must be positive")]
+    fn new_rejects_a_zero_step() {
+        AxisRange::new(0.0, 1.0, 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "is empty")]
+    fn new_rejects_an_empty_range() {
+        AxisRange::new(1.0, 1.0, 0.5);
+    }
+
+    #[test]
+    fn empty_ranges_map_to_the_middle() {
+        let system = CoordinateSystem {
+            x_range: AxisRange {
+                min: 2.0,
+                max: 2.0,
+                step: 1.0,
+            },
+            y_range: AxisRange::new(0.0, 4.0, 1.0),
+            width: 10.0,
+            height: 4.0,
+        };
+
+        assert_eq!(system.coords_to_point(2.0, 1.0), pos2(0.0, 1.0));
+    }
+
+    #[test]
+    fn format_tick_uses_as_many_decimals_as_the_step() {
+        assert_eq!(format_tick(3.0, 1.0), "$3$");
+        assert_eq!(format_tick(-2.0, 2.0), "$-2$");
+        assert_eq!(format_tick(0.5, 0.5), "$0.5$");
+        assert_eq!(format_tick(1.0, 0.25), "$1.00$");
+        assert_eq!(format_tick(0.1 + 0.2, 0.1), "$0.3$");
+    }
+}
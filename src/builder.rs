@@ -1,9 +1,9 @@
 use crate::{
     animation::Animation,
     component::{Component, Handle},
-    dynamics::{DynamicObject, OwnedDynamic},
-    motion::AddObject,
-    object::{Object, ObjectId},
+    dynamics::{Dynamic, DynamicObject, OwnedDynamic},
+    motion::{AddObject, AddUpdater},
+    object::ObjectId,
 };
 
 pub trait Builder: Sized {
@@ -19,9 +19,28 @@ pub trait Builder: Sized {
 
         let handle = component.build(&mut component_builder);
 
-        let object = DynamicObject::new_group(component_builder.objects).with_transform(transform);
+        let object =
+            DynamicObject::new_group(component_builder.objects).with_transform(transform.clone());
         let object_id = self.add_object(object);
 
+        // Transforms that depend on the world, e.g., on where other objects
+        // are, are resolved again once the motions of the frame have moved
+        // those. What they resolve to is in world coordinates, so it is
+        // mapped back through the parents before being stored.
+        if !transform.is_constant() {
+            self.play(AddUpdater::new(move |world| {
+                let Some(current) = world.objects.get(&object_id).map(|object| object.transform)
+                else {
+                    return;
+                };
+                let parent = world.objects.flattened_transform(object_id);
+                let mut resolved = parent.and_then(&current);
+                transform.update(world, &mut resolved);
+                world.objects.get_mut(&object_id).unwrap().transform =
+                    parent.inverse().and_then(&resolved);
+            }));
+        }
+
         Handle {
             inner: handle,
             object_id,
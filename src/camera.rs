@@ -0,0 +1,332 @@
+use egui::{pos2, vec2, Pos2, Rect, Vec2};
+
+use crate::{
+    component::{Component, Handle},
+    dynamics::{Dynamic, OwnedDynamic},
+    interpolation::Interpolatable,
+    motion::{Alpha, Motion},
+    object::Transform,
+    tracking::BoundsOf,
+    world::World,
+};
+
+/// How many scene units tall the view is at zoom 1.
+pub const UNIT_GRID_HEIGHT: f32 = 8.0;
+
+/// What part of the scene is shown. At zoom 1 the view is
+/// [`UNIT_GRID_HEIGHT`] units tall, centered on `position`, and it turns
+/// with `rotation`, in radians.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Camera {
+    pub position: Pos2,
+    pub zoom: f32,
+    pub rotation: f32,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self {
+            position: Pos2::ZERO,
+            zoom: 1.0,
+            rotation: 0.0,
+        }
+    }
+}
+
+impl Interpolatable for Camera {
+    fn interpolate(&self, other: &Self, alpha: f32) -> Self {
+        Self {
+            position: self.position + (other.position - self.position) * alpha,
+            zoom: interpolate_zoom(self.zoom, other.zoom, alpha),
+            rotation: self.rotation.interpolate(&other.rotation, alpha),
+        }
+    }
+}
+
+/// Zooming by the same factor should take the same time whether zooming in
+/// or out, so the zoom is interpolated geometrically.
+fn interpolate_zoom(from: f32, to: f32, alpha: f32) -> f32 {
+    if from > 0.0 && to > 0.0 {
+        from * (to / from).powf(alpha)
+    } else {
+        from.interpolate(&to, alpha)
+    }
+}
+
+fn rotate(v: Vec2, angle: f32) -> Vec2 {
+    let (sin, cos) = angle.sin_cos();
+    vec2(v.x * cos - v.y * sin, v.x * sin + v.y * cos)
+}
+
+/// An affine map `p -> matrix * p + offset`, with the matrix in column-major
+/// order, as the shader expects it.
+#[derive(Clone, Copy, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
+pub struct Affine2 {
+    pub matrix: [f32; 4],
+    pub offset: [f32; 2],
+}
+
+impl Affine2 {
+    pub fn apply(&self, p: Pos2) -> Pos2 {
+        let [a, b, c, d] = self.matrix;
+        pos2(
+            a * p.x + c * p.y + self.offset[0],
+            b * p.x + d * p.y + self.offset[1],
+        )
+    }
+
+    /// The map that undoes `self`, if it can be undone.
+    pub fn inverse(&self) -> Option<Affine2> {
+        let [a, b, c, d] = self.matrix;
+        let determinant = a * d - b * c;
+        if determinant == 0.0 || !determinant.is_finite() {
+            return None;
+        }
+
+        let matrix = [
+            d / determinant,
+            -b / determinant,
+            -c / determinant,
+            a / determinant,
+        ];
+        let linear = Affine2 {
+            matrix,
+            offset: [0.0, 0.0],
+        };
+        let offset = linear.apply(pos2(self.offset[0], self.offset[1]));
+
+        Some(Affine2 {
+            matrix,
+            offset: [-offset.x, -offset.y],
+        })
+    }
+
+    /// The map that applies `self` and then `next`.
+    pub fn then(&self, next: &Affine2) -> Affine2 {
+        let [a, b, c, d] = self.matrix;
+        let [e, f, g, h] = next.matrix;
+        let offset = next.apply(pos2(self.offset[0], self.offset[1]));
+
+        Affine2 {
+            matrix: [e * a + g * b, f * a + h * b, e * c + g * d, f * c + h * d],
+            offset: [offset.x, offset.y],
+        }
+    }
+}
+
+/// The transform of an object as the vertex shader applies it, turning
+/// around its anchor.
+impl From<Transform> for Affine2 {
+    fn from(transform: Transform) -> Self {
+        let (sin, cos) = transform.rotation.sin_cos();
+        let scale = transform.scale;
+        let rotated = Affine2 {
+            matrix: [cos * scale, sin * scale, -sin * scale, cos * scale],
+            offset: [0.0, 0.0],
+        };
+        let offset = transform.position + transform.anchor.to_vec2()
+            - rotated.apply(transform.anchor).to_vec2();
+
+        Affine2 {
+            offset: [offset.x, offset.y],
+            ..rotated
+        }
+    }
+}
+
+impl Camera {
+    /// How many pixels a scene unit takes up at this zoom.
+    pub fn pixels_per_unit(&self, render_size: (f32, f32)) -> f32 {
+        render_size.1 / UNIT_GRID_HEIGHT * self.zoom
+    }
+
+    /// Maps scene coordinates to pixels from the top left corner of the
+    /// view. Every renderer and overlay should go through this, so they all
+    /// agree on where things are.
+    pub fn world_to_screen(&self, render_size: (f32, f32)) -> Affine2 {
+        let scale = self.pixels_per_unit(render_size);
+        let (sin, cos) = (-self.rotation).sin_cos();
+        let matrix = [cos * scale, sin * scale, -sin * scale, cos * scale];
+
+        let view = Affine2 {
+            matrix,
+            offset: [0.0, 0.0],
+        };
+        let center = view.apply(self.position);
+
+        Affine2 {
+            matrix,
+            offset: [
+                render_size.0 / 2.0 - center.x,
+                render_size.1 / 2.0 - center.y,
+            ],
+        }
+    }
+
+    /// Maps scene coordinates to clip space, which is y-up and spans -1 to 1
+    /// across the view.
+    pub fn world_to_clip(&self, render_size: (f32, f32)) -> Affine2 {
+        let (width, height) = render_size;
+        let screen_to_clip = Affine2 {
+            matrix: [2.0 / width, 0.0, 0.0, -2.0 / height],
+            offset: [-1.0, 1.0],
+        };
+
+        self.world_to_screen(render_size).then(&screen_to_clip)
+    }
+
+    /// Maps pixels from the top left corner of the view back to scene
+    /// coordinates, e.g., for mouse input.
+    pub fn screen_to_world(&self, point: Pos2, render_size: (f32, f32)) -> Pos2 {
+        let centered = point - pos2(render_size.0 / 2.0, render_size.1 / 2.0);
+        self.position + rotate(centered / self.pixels_per_unit(render_size), self.rotation)
+    }
+
+    /// The part of the scene that is visible, in scene coordinates. When the
+    /// camera is rotated, this is the bounding box of the view.
+    pub fn visible_rect(&self, render_size: (f32, f32)) -> Rect {
+        // Before the view has a size, show a square.
+        let render_size = if render_size.1 > 0.0 {
+            render_size
+        } else {
+            (1.0, 1.0)
+        };
+        let (width, height) = render_size;
+        let corners = [
+            pos2(0.0, 0.0),
+            pos2(width, 0.0),
+            pos2(0.0, height),
+            pos2(width, height),
+        ];
+
+        Rect::from_points(&corners.map(|corner| self.screen_to_world(corner, render_size)))
+    }
+}
+
+/// Builds motions that move the camera of the scene. Get it from
+/// [`SceneBuilder::camera`](crate::scene::SceneBuilder::camera).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CameraHandle;
+
+impl CameraHandle {
+    /// Pans the camera until it is centered on `position`.
+    pub fn move_to(&self, position: impl Dynamic<Pos2>) -> MoveCamera {
+        MoveCamera {
+            to: OwnedDynamic::new(position),
+        }
+    }
+
+    /// Pans the camera until it is centered on the object.
+    pub fn focus_on<C: Component>(&self, handle: &Handle<C>) -> MoveCamera {
+        let bounds = BoundsOf::new(handle);
+        self.move_to(move |world: &World| bounds.get(world).center())
+    }
+
+    pub fn zoom_to(&self, zoom: impl Dynamic<f32>) -> ZoomCamera {
+        ZoomCamera {
+            to: OwnedDynamic::new(zoom),
+        }
+    }
+
+    pub fn rotate_to(&self, rotation: impl Dynamic<f32>) -> RotateCamera {
+        RotateCamera {
+            to: OwnedDynamic::new(rotation),
+        }
+    }
+
+    /// Pans and zooms the camera until `bounds` fills the view, e.g., with
+    /// [`BoundsOf`]. The rotation of the camera is left as it is.
+    pub fn zoom_to_fit(&self, bounds: impl Dynamic<Rect>) -> ZoomToFit {
+        ZoomToFit {
+            bounds: OwnedDynamic::new(bounds),
+            margin: 0.5,
+        }
+    }
+
+    /// Brings the camera back to where it starts.
+    pub fn reset(&self) -> SetCamera {
+        SetCamera {
+            to: Camera::default(),
+        }
+    }
+}
+
+pub struct MoveCamera {
+    to: OwnedDynamic<Pos2>,
+}
+
+impl Motion for MoveCamera {
+    fn animate(&self, world: &mut World, alpha: Alpha) {
+        let to = self.to.get(world);
+        let from = world.camera.position;
+        world.camera.position = from + (to - from) * alpha;
+    }
+}
+
+pub struct ZoomCamera {
+    to: OwnedDynamic<f32>,
+}
+
+impl Motion for ZoomCamera {
+    fn animate(&self, world: &mut World, alpha: Alpha) {
+        let to = self.to.get(world);
+        world.camera.zoom = interpolate_zoom(world.camera.zoom, to, alpha);
+    }
+}
+
+pub struct RotateCamera {
+    to: OwnedDynamic<f32>,
+}
+
+impl Motion for RotateCamera {
+    fn animate(&self, world: &mut World, alpha: Alpha) {
+        let to = self.to.get(world);
+        world.camera.rotation = world.camera.rotation.interpolate(&to, alpha);
+    }
+}
+
+pub struct ZoomToFit {
+    bounds: OwnedDynamic<Rect>,
+    margin: f32,
+}
+
+impl ZoomToFit {
+    /// Space left around the bounds, in scene units.
+    pub fn with_margin(mut self, margin: f32) -> Self {
+        self.margin = margin;
+        self
+    }
+}
+
+impl Motion for ZoomToFit {
+    fn animate(&self, world: &mut World, alpha: Alpha) {
+        let bounds = self.bounds.get(world).expand(self.margin);
+        if !bounds.is_positive() {
+            return;
+        }
+
+        let (width, height) = world.render_size();
+        let aspect_ratio = if height > 0.0 { width / height } else { 1.0 };
+        let zoom = (UNIT_GRID_HEIGHT / bounds.height())
+            .min(UNIT_GRID_HEIGHT * aspect_ratio / bounds.width());
+
+        let to = Camera {
+            position: bounds.center(),
+            zoom,
+            rotation: world.camera.rotation,
+        };
+        world.camera = world.camera.interpolate(&to, alpha);
+    }
+}
+
+pub struct SetCamera {
+    to: Camera,
+}
+
+impl Motion for SetCamera {
+    fn animate(&self, world: &mut World, alpha: Alpha) {
+        world.camera = world.camera.interpolate(&self.to, alpha);
+    }
+}
@@ -0,0 +1,348 @@
+use enimate::{
+    export::{self, ClipSettings, ExportError, ExportSettings},
+    registry::SceneRegistry,
+    scene::Scene,
+    svg,
+};
+use std::{
+    fmt, fs,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+    process::ExitCode,
+};
+
+const USAGE: &str = "\
+usage:
+    enimate                        open the player
+    enimate list [--tag <tag>]     list the scenes, or those with a tag
+    enimate render <scene> [options]
+    enimate still <scene> [--time <seconds>] [options]
+
+options:
+    --size <width>x<height>        the resolution of the scene by default
+    --fps <frames per second>      30 by default
+    --from <seconds>               where to start, the start of the scene by default
+    --to <seconds>                 where to stop, the end of the scene by default
+    --time <seconds>               the time of a still, 0 by default
+    --format <png|y4m|gif|apng|svg>
+                                   png by default; png renders write a directory
+                                   with a frame per file
+    --out <path>                   named after the scene by default, or - for
+                                   standard output, except for png renders
+
+<scene> is the name of a scene, ignoring case, or its number in the list.";
+
+#[derive(Debug)]
+enum CliError {
+    Usage(String),
+    UnknownScene(String),
+    Export(ExportError),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Usage(reason) => write!(f, "{reason}\n\n{USAGE}"),
+            CliError::UnknownScene(name) => {
+                write!(f, "no scene named {name:?}, see `enimate list`")
+            }
+            CliError::Export(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl From<ExportError> for CliError {
+    fn from(error: ExportError) -> Self {
+        CliError::Export(error)
+    }
+}
+
+impl From<io::Error> for CliError {
+    fn from(error: io::Error) -> Self {
+        CliError::Export(error.into())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    Png,
+    Y4m,
+    Gif,
+    Apng,
+    Svg,
+}
+
+impl Format {
+    fn parse(name: &str) -> Result<Self, CliError> {
+        match name.to_ascii_lowercase().as_str() {
+            "png" => Ok(Format::Png),
+            "y4m" => Ok(Format::Y4m),
+            "gif" => Ok(Format::Gif),
+            "apng" => Ok(Format::Apng),
+            "svg" => Ok(Format::Svg),
+            _ => Err(CliError::Usage(format!("unknown format {name:?}"))),
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Png | Format::Apng => "png",
+            Format::Y4m => "y4m",
+            Format::Gif => "gif",
+            Format::Svg => "svg",
+        }
+    }
+}
+
+/// The options shared by `render` and `still`. Options that don't apply to
+/// a command are ignored.
+struct Options {
+    scene: String,
+    size: Option<(u32, u32)>,
+    settings: ExportSettings,
+    time: f32,
+    format: Format,
+    out: Option<PathBuf>,
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Result<Self, CliError> {
+        let mut scene = None;
+        let mut options = Options {
+            scene: String::new(),
+            size: None,
+            settings: ExportSettings::default(),
+            time: 0.0,
+            format: Format::Png,
+            out: None,
+        };
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if !arg.starts_with("--") {
+                if scene.replace(arg.clone()).is_some() {
+                    return Err(CliError::Usage(format!("unexpected argument {arg:?}")));
+                }
+                continue;
+            }
+
+            let value = args
+                .next()
+                .ok_or_else(|| CliError::Usage(format!("{arg} needs a value")))?;
+            match arg.as_str() {
+                "--size" => options.size = Some(parse_size(value)?),
+                "--fps" => options.settings.fps = parse_number(arg, value)?,
+                "--from" => options.settings.from = parse_number(arg, value)?,
+                "--to" => options.settings.to = Some(parse_number(arg, value)?),
+                "--time" => options.time = parse_number(arg, value)?,
+                "--format" => options.format = Format::parse(value)?,
+                "--out" => options.out = Some(value.into()),
+                _ => return Err(CliError::Usage(format!("unknown option {arg}"))),
+            }
+        }
+
+        options.scene = scene.ok_or_else(|| CliError::Usage("which scene?".into()))?;
+        Ok(options)
+    }
+
+    /// Where to write, named after the scene unless given.
+    fn out(&self, name: &str, suffix: &str) -> PathBuf {
+        self.out.clone().unwrap_or_else(|| {
+            let stem = name
+                .to_lowercase()
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|word| !word.is_empty())
+                .collect::<Vec<_>>()
+                .join("_");
+            PathBuf::from(format!("{stem}{suffix}"))
+        })
+    }
+}
+
+fn parse_number(option: &str, value: &str) -> Result<f32, CliError> {
+    value
+        .parse()
+        .map_err(|_| CliError::Usage(format!("{option} expects a number, not {value:?}")))
+}
+
+fn parse_size(value: &str) -> Result<(u32, u32), CliError> {
+    let invalid = || CliError::Usage(format!("--size expects e.g. 1280x720, not {value:?}"));
+    let (width, height) = value.split_once(['x', 'X']).ok_or_else(invalid)?;
+
+    Ok((
+        width.parse().map_err(|_| invalid())?,
+        height.parse().map_err(|_| invalid())?,
+    ))
+}
+
+/// Opens `path` for writing, or standard output for `-`.
+fn create(path: &PathBuf) -> Result<Box<dyn Write>, CliError> {
+    if path.as_os_str() == "-" {
+        Ok(Box::new(BufWriter::new(io::stdout().lock())))
+    } else {
+        Ok(Box::new(BufWriter::new(fs::File::create(path)?)))
+    }
+}
+
+fn report_progress(rendered: usize, total: usize) {
+    eprint!("\rrendering frame {rendered}/{total}");
+    if rendered == total {
+        eprintln!();
+    }
+}
+
+/// Looks up a scene by title or by its number in `enimate list`, and builds
+/// it. Returns its title, and export settings at its resolution unless
+/// another was asked for.
+fn find_scene<'a>(
+    registry: &'a SceneRegistry,
+    options: &Options,
+) -> Result<(&'static str, &'a Scene, ExportSettings), CliError> {
+    let index = match options.scene.parse::<usize>() {
+        Ok(number) => number.checked_sub(1),
+        Err(_) => registry.find(&options.scene),
+    }
+    .filter(|index| *index < registry.len())
+    .ok_or_else(|| CliError::UnknownScene(options.scene.clone()))?;
+
+    let entry = registry.entry(index);
+    let (width, height) = options.size.unwrap_or(entry.resolution);
+    let settings = ExportSettings {
+        width,
+        height,
+        ..options.settings.clone()
+    };
+
+    Ok((entry.title, registry.scene(index), settings))
+}
+
+fn list(registry: &SceneRegistry, args: &[String]) -> Result<(), CliError> {
+    let tag = match args {
+        [] => None,
+        [option, tag] if option == "--tag" => Some(tag),
+        _ => return Err(CliError::Usage("list only takes --tag <tag>".into())),
+    };
+
+    for (number, entry) in registry.entries().iter().enumerate() {
+        if tag.is_some_and(|tag| !entry.has_tag(tag)) {
+            continue;
+        }
+
+        let (width, height) = entry.resolution;
+        println!(
+            "{:>3}  {:<32} {:>9}  {}",
+            number + 1,
+            entry.title,
+            format!("{width}x{height}"),
+            entry.tags.join(", ")
+        );
+    }
+
+    Ok(())
+}
+
+fn render(registry: &SceneRegistry, args: &[String]) -> Result<(), CliError> {
+    let options = Options::parse(args)?;
+    if matches!(options.format, Format::Png) && options.out.as_deref() == Some("-".as_ref()) {
+        return Err(CliError::Usage(
+            "png renders write a directory of frames, so they can't go to standard output, \
+             use another --format or --out <directory>"
+                .into(),
+        ));
+    }
+    let (name, scene, settings) = find_scene(registry, &options)?;
+
+    let settings = settings.with_progress(report_progress);
+    let clip = ClipSettings::default();
+
+    let out = match options.format {
+        Format::Png => options.out(name, ""),
+        format => options.out(name, &format!(".{}", format.extension())),
+    };
+    eprintln!("rendering {name} to {}", out.display());
+
+    match options.format {
+        Format::Png => {
+            export::export_png_sequence(scene, &settings, &out)?;
+        }
+        Format::Y4m => export::write_y4m(scene, &settings, create(&out)?)?,
+        Format::Gif => export::write_gif(scene, &settings, &clip, create(&out)?)?,
+        Format::Apng => export::write_apng(scene, &settings, &clip, create(&out)?)?,
+        Format::Svg => svg::write_animated_svg(scene, &settings, &clip, create(&out)?)?,
+    }
+
+    Ok(())
+}
+
+fn still(registry: &SceneRegistry, args: &[String]) -> Result<(), CliError> {
+    let options = Options::parse(args)?;
+    let (name, scene, settings) = find_scene(registry, &options)?;
+    let settings = &settings;
+    settings.validate()?;
+
+    let out = options.out(name, &format!(".{}", options.format.extension()));
+    eprintln!("rendering {name} at {}s to {}", options.time, out.display());
+
+    match options.format {
+        Format::Png => {
+            let render_size = (settings.width as f32, settings.height as f32);
+            let snapshot = scene.render_at(options.time, render_size);
+            let image = settings.rasterizer().render(&snapshot);
+            image.write_png(create(&out)?).map_err(ExportError::from)?;
+        }
+        Format::Svg => svg::write_svg(scene, options.time, settings, create(&out)?)?,
+        format => {
+            return Err(CliError::Usage(format!(
+                "stills are png or svg, not {format:?}"
+            )))
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs the command in `args`, without the program name. Only the scene
+/// that is rendered gets built.
+pub fn run(args: &[String], registry: SceneRegistry) -> ExitCode {
+    let result = match args[0].as_str() {
+        "list" => list(&registry, &args[1..]),
+        "render" => render(&registry, &args[1..]),
+        "still" => still(&registry, &args[1..]),
+        "help" | "--help" | "-h" => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        command => Err(CliError::Usage(format!("unknown command {command:?}"))),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn png_renders_cannot_go_to_standard_output() {
+        let registry = SceneRegistry::collect();
+
+        for format in [&[][..], &["--format", "png"]] {
+            let args = args(&[&["1", "--out", "-"], format].concat());
+            let result = render(&registry, &args);
+            assert!(
+                matches!(&result, Err(CliError::Usage(reason)) if reason.contains("standard output")),
+                "{result:?}"
+            );
+        }
+    }
+}
@@ -1,10 +1,10 @@
-use std::ops::Deref;
+use std::ops::{Deref, DerefMut};
 
 use egui::Pos2;
 
 use crate::builder::Builder;
 use crate::dynamics::{Dynamic, DynamicTransform, OwnedDynamic};
-use crate::motion::{FadeIn, Move, MoveTo};
+use crate::motion::{FadeIn, FadeOut, Move, MoveTo};
 use crate::object::{Object, ObjectId, Transform};
 
 pub struct Handle<C: Component> {
@@ -20,6 +20,12 @@ impl<C: Component> Deref for Handle<C> {
     }
 }
 
+impl<C: Component> DerefMut for Handle<C> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
 impl<H: Clone, C: Component<Handle = H>> Clone for Handle<C> {
     fn clone(&self) -> Self {
         Self {
@@ -49,14 +55,16 @@ pub trait ComponentExt: Component + Sized {
             transform: transform.into(),
         }
     }
-    fn with_position(self, position: Pos2) -> ComponentWithTransform<Self> {
-        self.with_transform(Transform::default().with_position(position))
+    fn with_position(self, position: impl Dynamic<Pos2>) -> ComponentWithTransform<Self> {
+        self.with_transform(Transform::default())
+            .with_position(position)
     }
-    fn with_rotation(self, rotation: f32) -> ComponentWithTransform<Self> {
-        self.with_transform(Transform::default().with_rotation(rotation))
+    fn with_rotation(self, rotation: impl Dynamic<f32>) -> ComponentWithTransform<Self> {
+        self.with_transform(Transform::default())
+            .with_rotation(rotation)
     }
-    fn with_scale(self, scale: f32) -> ComponentWithTransform<Self> {
-        self.with_transform(Transform::default().with_scale(scale))
+    fn with_scale(self, scale: impl Dynamic<f32>) -> ComponentWithTransform<Self> {
+        self.with_transform(Transform::default()).with_scale(scale)
     }
     fn with_anchor(self, anchor: Pos2) -> ComponentWithTransform<Self> {
         self.with_transform(Transform::default().with_anchor(anchor))
@@ -82,6 +90,15 @@ impl<C: Component> Component for ComponentWithTransform<C> {
     }
 }
 
+impl<C: Component> From<Handle<ComponentWithTransform<C>>> for Handle<C> {
+    fn from(handle: Handle<ComponentWithTransform<C>>) -> Self {
+        Handle {
+            inner: handle.inner,
+            object_id: handle.object_id,
+        }
+    }
+}
+
 impl<C: Component> ComponentWithTransform<C> {
     pub fn with_transform(mut self, transform: impl Into<DynamicTransform>) -> Self {
         self.transform = transform.into();
@@ -122,6 +139,12 @@ impl<C: Component> Handle<C> {
             object_id: self.object_id,
         }
     }
+
+    pub fn fade_out(&self) -> FadeOut {
+        FadeOut {
+            object_id: self.object_id,
+        }
+    }
 }
 
 impl Component for Object {
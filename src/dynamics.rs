@@ -1,13 +1,24 @@
 use dyn_clone::{clone_box, DynClone};
-use egui::Pos2;
+use egui::{Pos2, Vec2};
+use std::{
+    f32::consts::TAU,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
 
 use crate::{
+    interpolation::Interpolatable,
     object::{Object, ObjectId, ObjectKind, Transform},
-    world::{Variable, World},
+    world::{Var, VariableType, World},
 };
 
 pub trait Dynamic<T: 'static>: DynClone + 'static {
     fn get(&self, world: &World) -> T;
+
+    /// Whether the value is the same whatever the world, e.g., a plain
+    /// number, so that it doesn't need to be resolved again every frame.
+    fn is_constant(&self) -> bool {
+        false
+    }
 }
 
 pub struct OwnedDynamic<T: 'static>(Box<dyn Dynamic<T>>);
@@ -20,6 +31,10 @@ impl<T: 'static> OwnedDynamic<T> {
     pub fn get(&self, world: &World) -> T {
         self.0.get(world)
     }
+
+    pub fn is_constant(&self) -> bool {
+        self.0.is_constant()
+    }
 }
 
 impl<T: 'static> Clone for OwnedDynamic<T> {
@@ -28,14 +43,116 @@ impl<T: 'static> Clone for OwnedDynamic<T> {
     }
 }
 
+impl<T: 'static> Dynamic<T> for OwnedDynamic<T> {
+    fn get(&self, world: &World) -> T {
+        self.0.get(world)
+    }
+
+    fn is_constant(&self) -> bool {
+        self.0.is_constant()
+    }
+}
+
+/// Any cloneable closure of the world is a dynamic value, e.g.,
+/// `move |world: &World| radius.get(world) * 2.0`.
+impl<T: 'static, F> Dynamic<T> for F
+where
+    F: Fn(&World) -> T + Clone + 'static,
+{
+    fn get(&self, world: &World) -> T {
+        self(world)
+    }
+}
+
+pub trait DynamicExt<T: 'static>: Dynamic<T> + Sized {
+    fn owned(self) -> OwnedDynamic<T> {
+        OwnedDynamic::new(self)
+    }
+
+    fn map<U: 'static>(self, f: impl Fn(T) -> U + Clone + 'static) -> OwnedDynamic<U> {
+        let source = self.owned();
+
+        OwnedDynamic::new(move |world: &World| f(source.get(world)))
+    }
+
+    fn zip<U: 'static>(self, other: impl Dynamic<U>) -> OwnedDynamic<(T, U)> {
+        zip(self, other)
+    }
+}
+
+impl<T: 'static, D: Dynamic<T>> DynamicExt<T> for D {}
+
+/// Pairs up two dynamic values, to be combined with [`DynamicExt::map`].
+pub fn zip<A: 'static, B: 'static>(a: impl Dynamic<A>, b: impl Dynamic<B>) -> OwnedDynamic<(A, B)> {
+    let (a, b) = (a.owned(), b.owned());
+
+    OwnedDynamic::new(move |world: &World| (a.get(world), b.get(world)))
+}
+
+/// Implements an operator between a dynamic value and anything dynamic of the
+/// right-hand type, evaluated every time the result is.
+macro_rules! dynamic_op {
+    ($trait:ident, $method:ident, $lhs:ty, $rhs:ty, $output:ty) => {
+        impl<D: Dynamic<$rhs>> $trait<D> for OwnedDynamic<$lhs> {
+            type Output = OwnedDynamic<$output>;
+
+            fn $method(self, rhs: D) -> Self::Output {
+                zip(self, rhs).map(|(lhs, rhs)| $trait::$method(lhs, rhs))
+            }
+        }
+    };
+}
+
+dynamic_op!(Add, add, f32, f32, f32);
+dynamic_op!(Sub, sub, f32, f32, f32);
+dynamic_op!(Mul, mul, f32, f32, f32);
+dynamic_op!(Div, div, f32, f32, f32);
+dynamic_op!(Add, add, Pos2, Vec2, Pos2);
+dynamic_op!(Sub, sub, Pos2, Vec2, Pos2);
+dynamic_op!(Add, add, Vec2, Vec2, Vec2);
+dynamic_op!(Sub, sub, Vec2, Vec2, Vec2);
+dynamic_op!(Mul, mul, Vec2, f32, Vec2);
+dynamic_op!(Div, div, Vec2, f32, Vec2);
+
+impl Neg for OwnedDynamic<f32> {
+    type Output = OwnedDynamic<f32>;
+
+    fn neg(self) -> Self::Output {
+        self.map(|value| -value)
+    }
+}
+
+impl Neg for OwnedDynamic<Vec2> {
+    type Output = OwnedDynamic<Vec2>;
+
+    fn neg(self) -> Self::Output {
+        self.map(|value| -value)
+    }
+}
+
+impl Mul<OwnedDynamic<f32>> for f32 {
+    type Output = OwnedDynamic<f32>;
+
+    fn mul(self, rhs: OwnedDynamic<f32>) -> Self::Output {
+        rhs.map(move |rhs| self * rhs)
+    }
+}
+
+impl OwnedDynamic<Pos2> {
+    /// Scales the point away from the origin.
+    pub fn scale(self, factor: impl Dynamic<f32>) -> OwnedDynamic<Pos2> {
+        zip(self, factor).map(|(point, factor)| (point.to_vec2() * factor).to_pos2())
+    }
+}
+
 // impl<T> DynamicType<T> for Dynamic<T> {
 //     fn get(&self, world: &World) -> T {
 //         self.0.get(world)
 //     }
 // }
 
-impl Dynamic<f32> for Variable {
-    fn get(&self, world: &World) -> f32 {
+impl<T: VariableType> Dynamic<T> for Var<T> {
+    fn get(&self, world: &World) -> T {
         world.get_variable(*self)
     }
 }
@@ -71,14 +188,160 @@ impl Dynamic<f32> for f32 {
     fn get(&self, _: &World) -> f32 {
         *self
     }
+    fn is_constant(&self) -> bool {
+        true
+    }
 }
 
 impl Dynamic<Pos2> for Pos2 {
     fn get(&self, _: &World) -> Pos2 {
         *self
     }
+    fn is_constant(&self) -> bool {
+        true
+    }
+}
+
+impl Dynamic<Vec2> for Vec2 {
+    fn get(&self, _: &World) -> Vec2 {
+        *self
+    }
+    fn is_constant(&self) -> bool {
+        true
+    }
+}
+
+/// The scene time in seconds.
+#[derive(Clone, Copy, Debug)]
+pub enum SceneTime {
+    /// The time within the scene being rendered, starting when an embedded
+    /// scene does.
+    Local,
+    /// The time within the outermost scene.
+    Absolute,
+}
+
+impl Dynamic<f32> for SceneTime {
+    fn get(&self, world: &World) -> f32 {
+        match self {
+            SceneTime::Local => world.time(),
+            SceneTime::Absolute => world.absolute_time(),
+        }
+    }
 }
 
+/// A sine wave over the scene time: `center + amplitude * sin(2π * frequency
+/// * t + phase)`.
+#[derive(Clone, Copy, Debug)]
+pub struct Oscillator {
+    pub amplitude: f32,
+    pub frequency: f32,
+    pub phase: f32,
+    pub center: f32,
+    pub time: SceneTime,
+}
+
+impl Oscillator {
+    pub fn new(amplitude: f32, frequency: f32) -> Self {
+        Self {
+            amplitude,
+            frequency,
+            phase: 0.0,
+            center: 0.0,
+            time: SceneTime::Local,
+        }
+    }
+
+    pub fn with_phase(mut self, phase: f32) -> Self {
+        self.phase = phase;
+        self
+    }
+
+    pub fn with_center(mut self, center: f32) -> Self {
+        self.center = center;
+        self
+    }
+
+    pub fn with_time(mut self, time: SceneTime) -> Self {
+        self.time = time;
+        self
+    }
+}
+
+impl Dynamic<f32> for Oscillator {
+    fn get(&self, world: &World) -> f32 {
+        let t = self.time.get(world);
+
+        self.center + self.amplitude * (TAU * self.frequency * t + self.phase).sin()
+    }
+}
+
+/// Smooth gradient noise over the scene time, between `-amplitude` and
+/// `amplitude`. The same seed always gives the same values, so any frame can
+/// be rendered on its own.
+#[derive(Clone, Copy, Debug)]
+pub struct Noise {
+    pub seed: u32,
+    pub amplitude: f32,
+    /// How many times a second the noise changes direction, on average.
+    pub frequency: f32,
+    pub time: SceneTime,
+}
+
+impl Noise {
+    pub fn new(seed: u32, amplitude: f32, frequency: f32) -> Self {
+        Self {
+            seed,
+            amplitude,
+            frequency,
+            time: SceneTime::Local,
+        }
+    }
+
+    pub fn with_time(mut self, time: SceneTime) -> Self {
+        self.time = time;
+        self
+    }
+
+    /// A gradient between -1 and 1 for every lattice point.
+    fn gradient(&self, lattice: i32) -> f32 {
+        let mut hash = (lattice as u32) ^ self.seed.wrapping_mul(0x9e37_79b9);
+        hash = (hash ^ (hash >> 16)).wrapping_mul(0x7feb_352d);
+        hash = (hash ^ (hash >> 15)).wrapping_mul(0x846c_a68b);
+        hash ^= hash >> 16;
+
+        hash as f32 / u32::MAX as f32 * 2.0 - 1.0
+    }
+
+    pub fn sample(&self, x: f32) -> f32 {
+        // Gradient noise is zero on the lattice, so every seed gets its own
+        // offset to keep noises with different seeds from all crossing zero
+        // at the same time.
+        let x = x + self.gradient(i32::MIN) * 0.5 + 0.5;
+
+        let lattice = x.floor();
+        let offset = x - lattice;
+        let lattice = lattice as i32;
+
+        let from = self.gradient(lattice) * offset;
+        let to = self.gradient(lattice.wrapping_add(1)) * (offset - 1.0);
+        let fade = offset * offset * offset * (offset * (offset * 6.0 - 15.0) + 10.0);
+
+        // 1D gradient noise stays within -0.5 and 0.5.
+        from.interpolate(&to, fade) * 2.0
+    }
+}
+
+impl Dynamic<f32> for Noise {
+    fn get(&self, world: &World) -> f32 {
+        self.amplitude * self.sample(self.time.get(world) * self.frequency)
+    }
+}
+
+/// A transform whose parts can each be dynamic. Parts that aren't constant
+/// are resolved again after the motions of every frame, so an object placed
+/// with, e.g., [`PositionOf`](crate::tracking::PositionOf) follows its target
+/// and overrides motions of that part of its own transform.
 #[derive(Clone)]
 pub struct DynamicTransform {
     pub position: OwnedDynamic<Pos2>,
@@ -96,12 +359,41 @@ impl Dynamic<Transform> for DynamicTransform {
             anchor: self.anchor.get(world),
         }
     }
+
+    fn is_constant(&self) -> bool {
+        self.position.is_constant()
+            && self.scale.is_constant()
+            && self.rotation.is_constant()
+            && self.anchor.is_constant()
+    }
+}
+
+impl DynamicTransform {
+    /// Resolves the parts that aren't constant into `transform`, leaving the
+    /// others as motions left them.
+    pub fn update(&self, world: &World, transform: &mut Transform) {
+        if !self.position.is_constant() {
+            transform.position = self.position.get(world);
+        }
+        if !self.scale.is_constant() {
+            transform.scale = self.scale.get(world);
+        }
+        if !self.rotation.is_constant() {
+            transform.rotation = self.rotation.get(world);
+        }
+        if !self.anchor.is_constant() {
+            transform.anchor = self.anchor.get(world);
+        }
+    }
 }
 
 impl Dynamic<Transform> for Transform {
     fn get(&self, _: &World) -> Transform {
         *self
     }
+    fn is_constant(&self) -> bool {
+        true
+    }
 }
 
 impl From<Transform> for DynamicTransform {
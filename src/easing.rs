@@ -1,16 +1,12 @@
+#[derive(Default)]
 pub enum Easing {
+    #[default]
     Linear,
     EaseIn,
     EaseOut,
     EaseInOut,
 }
 
-impl Default for Easing {
-    fn default() -> Self {
-        Easing::Linear
-    }
-}
-
 impl Easing {
     pub fn apply(&self, t: f32) -> f32 {
         match self {
@@ -0,0 +1,841 @@
+use color_quant::NeuQuant;
+use egui::Color32;
+use std::{
+    fmt, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    rasterizer::{Image, Rasterizer},
+    scene::Scene,
+    timing::Time,
+};
+
+#[derive(Debug)]
+pub enum ExportError {
+    Io(io::Error),
+    Png(png::EncodingError),
+    Gif(gif::EncodingError),
+    InvalidSettings(String),
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::Io(error) => write!(f, "i/o error: {error}"),
+            ExportError::Png(error) => write!(f, "png encoding failed: {error}"),
+            ExportError::Gif(error) => write!(f, "gif encoding failed: {error}"),
+            ExportError::InvalidSettings(reason) => write!(f, "invalid export settings: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+impl From<io::Error> for ExportError {
+    fn from(error: io::Error) -> Self {
+        ExportError::Io(error)
+    }
+}
+
+impl From<png::EncodingError> for ExportError {
+    fn from(error: png::EncodingError) -> Self {
+        ExportError::Png(error)
+    }
+}
+
+impl From<gif::EncodingError> for ExportError {
+    fn from(error: gif::EncodingError) -> Self {
+        ExportError::Gif(error)
+    }
+}
+
+/// Which frames of a scene to export, and how to render them.
+#[derive(Clone, Debug)]
+pub struct ExportSettings {
+    pub width: u32,
+    pub height: u32,
+    pub fps: f32,
+    pub from: Time,
+    /// Until the end of the scene if `None`.
+    pub to: Option<Time>,
+    pub background: Color32,
+    /// Called with the number of frames rendered so far and the total, after
+    /// each frame, e.g., to show a progress bar.
+    pub progress: Option<fn(usize, usize)>,
+}
+
+impl Default for ExportSettings {
+    fn default() -> Self {
+        Self {
+            width: 1920,
+            height: 1080,
+            fps: 30.0,
+            from: 0.0,
+            to: None,
+            background: Color32::BLACK,
+            progress: None,
+        }
+    }
+}
+
+impl ExportSettings {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_fps(mut self, fps: f32) -> Self {
+        self.fps = fps;
+        self
+    }
+
+    pub fn with_range(mut self, from: Time, to: Time) -> Self {
+        self.from = from;
+        self.to = Some(to);
+        self
+    }
+
+    pub fn with_background(mut self, background: Color32) -> Self {
+        self.background = background;
+        self
+    }
+
+    pub fn with_progress(mut self, progress: fn(usize, usize)) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), ExportError> {
+        let invalid = |reason: &str| Err(ExportError::InvalidSettings(reason.into()));
+
+        if self.width == 0 || self.height == 0 {
+            return invalid("the size must not be zero");
+        }
+        if !(self.fps.is_finite() && self.fps > 0.0) {
+            return invalid("the frame rate must be positive");
+        }
+        if !self.from.is_finite() || self.from < 0.0 {
+            return invalid("the range must not start before the scene");
+        }
+        if matches!(self.to, Some(to) if to.is_nan() || to < self.from) {
+            return invalid("the range must not end before it starts");
+        }
+
+        Ok(())
+    }
+
+    /// The times of the frames to export, `1 / fps` apart, from the start of
+    /// the range up to and including its end, so the last frame shows how
+    /// the range ends. Each time is computed from its frame index rather than
+    /// by adding up frame durations, so frames land exactly where they
+    /// should however long the scene is.
+    pub fn frame_times(&self, scene: &Scene) -> Vec<Time> {
+        let from = self.from.min(scene.length()) as f64;
+        let to = self.to.unwrap_or(scene.length()).min(scene.length()) as f64;
+        let fps = self.fps as f64;
+
+        // Allow for rounding errors in the range, so that e.g. a range of one
+        // second at 30 fps has 31 frames rather than 30.
+        let intervals = ((to - from).max(0.0) * fps + 1e-6).floor() as usize;
+
+        (0..=intervals)
+            .map(|i| (from + i as f64 / fps) as Time)
+            .collect()
+    }
+
+    pub fn rasterizer(&self) -> Rasterizer {
+        Rasterizer::new(self.width, self.height).with_background(self.background)
+    }
+}
+
+pub struct Frame {
+    pub index: usize,
+    pub time: Time,
+    pub image: Image,
+}
+
+/// Renders the frames of a scene one at a time, as they are needed.
+pub fn frames<'a>(
+    scene: &'a Scene,
+    settings: &ExportSettings,
+) -> Result<impl ExactSizeIterator<Item = Frame> + 'a, ExportError> {
+    settings.validate()?;
+
+    let rasterizer = settings.rasterizer();
+    let render_size = (settings.width as f32, settings.height as f32);
+    let progress = settings.progress;
+    let times = settings.frame_times(scene);
+    let total = times.len();
+
+    Ok(times.into_iter().enumerate().map(move |(index, time)| {
+        let image = rasterizer.render(&scene.render_at(time, render_size));
+        if let Some(progress) = progress {
+            progress(index + 1, total);
+        }
+
+        Frame { index, time, image }
+    }))
+}
+
+/// Writes each frame as a PNG into `directory`, which is created if needed,
+/// named `frame_00000.png`, `frame_00001.png`, and so on. Returns the paths
+/// of the frames in order.
+pub fn export_png_sequence(
+    scene: &Scene,
+    settings: &ExportSettings,
+    directory: impl AsRef<Path>,
+) -> Result<Vec<PathBuf>, ExportError> {
+    let directory = directory.as_ref();
+    fs::create_dir_all(directory)?;
+
+    let frames = frames(scene, settings)?;
+    let digits = frames.len().saturating_sub(1).to_string().len().max(5);
+
+    frames
+        .map(|frame| {
+            let path = directory.join(format!("frame_{:0digits$}.png", frame.index));
+            frame.image.save_png(&path)?;
+            Ok(path)
+        })
+        .collect()
+}
+
+/// Writes the frames one after the other as raw RGBA, with straight alpha
+/// and no header, e.g., to pipe into
+/// `ffmpeg -f rawvideo -pix_fmt rgba -s <width>x<height> -r <fps> -i -`.
+pub fn write_raw_rgba(
+    scene: &Scene,
+    settings: &ExportSettings,
+    mut writer: impl Write,
+) -> Result<(), ExportError> {
+    for frame in frames(scene, settings)? {
+        writer.write_all(&frame.image.data)?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Writes the frames as a YUV4MPEG2 stream, which most encoders read
+/// directly, e.g., `ffmpeg -i scene.y4m scene.mp4`. Colors are converted to
+/// limited range BT.601 YUV with 4:2:0 chroma subsampling. The stream has no
+/// alpha, so transparent pixels end up black.
+pub fn write_y4m(
+    scene: &Scene,
+    settings: &ExportSettings,
+    mut writer: impl Write,
+) -> Result<(), ExportError> {
+    let frames = frames(scene, settings)?;
+    let (numerator, denominator) = frame_rate_ratio(settings.fps);
+
+    writeln!(
+        writer,
+        "YUV4MPEG2 W{} H{} F{numerator}:{denominator} Ip A1:1 C420jpeg XCOLORRANGE=LIMITED",
+        settings.width, settings.height,
+    )?;
+
+    let mut planes = Vec::new();
+    for frame in frames {
+        writer.write_all(b"FRAME\n")?;
+        rgba_to_yuv420(&frame.image, &mut planes);
+        writer.write_all(&planes)?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// The frame rate as a fraction, exact for whole frame rates and to a
+/// thousandth of a frame otherwise, e.g., 29.97 becomes 2997:100. Rates that
+/// would round to zero become one frame per so many seconds instead, since
+/// a rate of zero isn't valid.
+fn frame_rate_ratio(fps: f32) -> (u32, u32) {
+    let fps = fps as f64;
+    if (fps * 1000.0).round() < 1.0 {
+        return (1, (1.0 / fps).round().clamp(1.0, u32::MAX as f64) as u32);
+    }
+
+    let numerator = (fps * 1000.0).round().min(u32::MAX as f64) as u32;
+    let denominator = 1000;
+
+    let mut a = numerator;
+    let mut b = denominator;
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    (numerator / a, denominator / a)
+}
+
+/// The color of a pixel composited onto black, in limited range BT.601.
+fn yuv(pixel: &[u8]) -> (f32, f32, f32) {
+    let alpha = pixel[3] as f32 / 255.0;
+    let [r, g, b] = [0, 1, 2].map(|i| pixel[i] as f32 * alpha);
+
+    let y = 16.0 + (65.481 * r + 128.553 * g + 24.966 * b) / 255.0;
+    let u = 128.0 + (-37.797 * r - 74.203 * g + 112.0 * b) / 255.0;
+    let v = 128.0 + (112.0 * r - 93.786 * g - 18.214 * b) / 255.0;
+
+    (y, u, v)
+}
+
+/// Converts an image into its Y, U and V planes, one after the other, with
+/// U and V at half the resolution in both directions. For odd sizes, the
+/// chroma planes round up and the last row or column is averaged on its own.
+fn rgba_to_yuv420(image: &Image, planes: &mut Vec<u8>) {
+    let width = image.width as usize;
+    let height = image.height as usize;
+    let chroma_width = width.div_ceil(2);
+    let chroma_height = height.div_ceil(2);
+
+    planes.clear();
+    planes.resize(width * height + 2 * chroma_width * chroma_height, 0);
+    let (luma, chroma) = planes.split_at_mut(width * height);
+    let (u_plane, v_plane) = chroma.split_at_mut(chroma_width * chroma_height);
+
+    let pixel = |x: usize, y: usize| yuv(&image.data[(y * width + x) * 4..][..4]);
+
+    for y in 0..height {
+        for x in 0..width {
+            luma[y * width + x] = pixel(x, y).0.round().clamp(0.0, 255.0) as u8;
+        }
+    }
+
+    for cy in 0..chroma_height {
+        for cx in 0..chroma_width {
+            let mut u = 0.0;
+            let mut v = 0.0;
+            let mut count = 0.0;
+
+            for y in (cy * 2)..(cy * 2 + 2).min(height) {
+                for x in (cx * 2)..(cx * 2 + 2).min(width) {
+                    let (_, pixel_u, pixel_v) = pixel(x, y);
+                    u += pixel_u;
+                    v += pixel_v;
+                    count += 1.0;
+                }
+            }
+
+            let i = cy * chroma_width + cx;
+            u_plane[i] = (u / count).round().clamp(0.0, 255.0) as u8;
+            v_plane[i] = (v / count).round().clamp(0.0, 255.0) as u8;
+        }
+    }
+}
+
+/// Settings for short looping clips, such as GIFs, on top of the
+/// [`ExportSettings`] of the frames.
+#[derive(Clone, Debug)]
+pub struct ClipSettings {
+    /// How many times the clip plays, at least once, or forever if `None`.
+    pub plays: Option<u16>,
+    /// Keeps only every `frame_step`-th frame, lowering the frame rate by as
+    /// much.
+    pub frame_step: usize,
+    /// The largest width and height of the clip. Larger clips are rendered
+    /// smaller, keeping their aspect ratio.
+    pub max_size: Option<(u32, u32)>,
+    /// Spreads the error of reducing the colors to a palette over
+    /// neighboring pixels, which hides banding in gradients. GIF only.
+    pub dither: bool,
+}
+
+impl Default for ClipSettings {
+    fn default() -> Self {
+        Self {
+            plays: None,
+            frame_step: 1,
+            max_size: None,
+            dither: false,
+        }
+    }
+}
+
+impl ClipSettings {
+    pub fn with_plays(mut self, plays: u16) -> Self {
+        self.plays = Some(plays);
+        self
+    }
+
+    pub fn with_frame_step(mut self, frame_step: usize) -> Self {
+        self.frame_step = frame_step;
+        self
+    }
+
+    pub fn with_max_size(mut self, width: u32, height: u32) -> Self {
+        self.max_size = Some((width, height));
+        self
+    }
+
+    pub fn with_dither(mut self, dither: bool) -> Self {
+        self.dither = dither;
+        self
+    }
+
+    /// The settings the frames of the clip are rendered with. Since scenes
+    /// are vector graphics, a smaller clip is rendered at its own size rather
+    /// than scaled down.
+    pub fn frame_settings(&self, settings: &ExportSettings) -> Result<ExportSettings, ExportError> {
+        if self.frame_step == 0 {
+            return Err(ExportError::InvalidSettings(
+                "the frame step must not be zero".into(),
+            ));
+        }
+        // Formats disagree on what playing zero times means, e.g., APNG
+        // takes it for forever, so that is `None` instead.
+        if self.plays == Some(0) {
+            return Err(ExportError::InvalidSettings(
+                "the clip must play at least once".into(),
+            ));
+        }
+
+        let mut settings = settings.clone();
+        settings.fps /= self.frame_step as f32;
+
+        if let Some((max_width, max_height)) = self.max_size {
+            let scale = (max_width as f32 / settings.width as f32)
+                .min(max_height as f32 / settings.height as f32);
+
+            if scale < 1.0 {
+                settings.width = ((settings.width as f32 * scale).round() as u32).max(1);
+                settings.height = ((settings.height as f32 * scale).round() as u32).max(1);
+            }
+        }
+
+        Ok(settings)
+    }
+}
+
+/// Writes the frames as a looping GIF, with a single palette of up to 256
+/// colors shared by all frames. Pixels that are less than half opaque are
+/// transparent. The frames are all rendered before encoding, to pick the
+/// palette, so this is meant for short clips.
+pub fn write_gif(
+    scene: &Scene,
+    settings: &ExportSettings,
+    clip: &ClipSettings,
+    writer: impl Write,
+) -> Result<(), ExportError> {
+    let settings = clip.frame_settings(settings)?;
+    let (Ok(width), Ok(height)) = (
+        u16::try_from(settings.width),
+        u16::try_from(settings.height),
+    ) else {
+        return Err(ExportError::InvalidSettings(
+            "gifs can't be larger than 65535 pixels".into(),
+        ));
+    };
+
+    let images: Vec<Image> = frames(scene, &settings)?.map(|frame| frame.image).collect();
+    let palette = GifPalette::new(&images);
+
+    let mut encoder = gif::Encoder::new(writer, width, height, &palette.colors)?;
+    // Without the looping extension, a gif plays once.
+    match clip.plays {
+        None => encoder.set_repeat(gif::Repeat::Infinite)?,
+        Some(plays) if plays > 1 => encoder.set_repeat(gif::Repeat::Finite(plays - 1))?,
+        Some(_) => {}
+    }
+
+    for (index, image) in images.iter().enumerate() {
+        let frame = gif::Frame {
+            width,
+            height,
+            delay: gif_delay(index, settings.fps),
+            // Clears the frame before the next one, so that what was drawn
+            // doesn't show through its transparent pixels.
+            dispose: gif::DisposalMethod::Background,
+            transparent: palette.transparent,
+            buffer: palette.indices(image, clip.dither).into(),
+            ..Default::default()
+        };
+        encoder.write_frame(&frame)?;
+    }
+
+    Ok(())
+}
+
+/// Gif delays are in hundredths of a second, so frames are rounded to them
+/// such that the clip as a whole keeps the right speed, e.g., at 30 fps the
+/// delays go 3, 4, 3, 3, 4, 3, and so on. Viewers slow down delays under 2,
+/// so frame rates over 50 fps play at 50.
+fn gif_delay(index: usize, fps: f32) -> u16 {
+    let at = |index: usize| (index as f64 * 100.0 / fps as f64).round() as u64;
+    (at(index + 1) - at(index)).clamp(2, u16::MAX as u64) as u16
+}
+
+struct GifPalette {
+    quantizer: NeuQuant,
+    /// The colors as RGB triplets.
+    colors: Vec<u8>,
+    transparent: Option<u8>,
+}
+
+impl GifPalette {
+    /// How many pixels the palette is learned from, at most, to keep long
+    /// clips quick to encode.
+    const SAMPLES: usize = 1 << 20;
+
+    fn new(images: &[Image]) -> Self {
+        let total = images
+            .iter()
+            .map(|image| image.data.len() / 4)
+            .sum::<usize>();
+        let step = total.div_ceil(Self::SAMPLES).max(1);
+
+        let mut samples = Vec::new();
+        let mut has_transparency = false;
+        for pixel in images
+            .iter()
+            .flat_map(|image| image.data.chunks_exact(4))
+            .step_by(step)
+        {
+            if pixel[3] < 128 {
+                has_transparency = true;
+            } else {
+                samples.extend_from_slice(&[pixel[0], pixel[1], pixel[2], 255]);
+            }
+        }
+
+        // The last color is kept for transparent pixels if there are any.
+        let colors = if has_transparency { 255 } else { 256 };
+        if samples.is_empty() {
+            samples.extend_from_slice(&[0, 0, 0, 255]);
+        }
+        let quantizer = NeuQuant::new(10, colors, &samples);
+
+        let mut palette = quantizer.color_map_rgb();
+        let transparent = has_transparency.then(|| {
+            palette.extend_from_slice(&[0, 0, 0]);
+            (palette.len() / 3 - 1) as u8
+        });
+
+        Self {
+            quantizer,
+            colors: palette,
+            transparent,
+        }
+    }
+
+    /// The palette index of each pixel, optionally with Floyd-Steinberg
+    /// dithering.
+    fn indices(&self, image: &Image, dither: bool) -> Vec<u8> {
+        let width = image.width as usize;
+        let height = image.height as usize;
+
+        let mut indices = vec![0; width * height];
+        // The error carried into the current and the next row, with a pixel
+        // of padding on both sides.
+        let mut errors = [vec![[0.0f32; 3]; width + 2], vec![[0.0f32; 3]; width + 2]];
+
+        for y in 0..height {
+            for x in 0..width {
+                let i = y * width + x;
+                let pixel = &image.data[i * 4..][..4];
+
+                if pixel[3] < 128 {
+                    if let Some(transparent) = self.transparent {
+                        indices[i] = transparent;
+                        continue;
+                    }
+                }
+
+                let error = errors[0][x + 1];
+                let wanted = [0, 1, 2].map(|c| {
+                    if dither {
+                        (pixel[c] as f32 + error[c]).clamp(0.0, 255.0)
+                    } else {
+                        pixel[c] as f32
+                    }
+                });
+
+                let index = self.quantizer.index_of(&[
+                    wanted[0].round() as u8,
+                    wanted[1].round() as u8,
+                    wanted[2].round() as u8,
+                    255,
+                ]);
+                indices[i] = index as u8;
+
+                if dither {
+                    let chosen = &self.colors[index * 3..][..3];
+                    for c in 0..3 {
+                        let error = wanted[c] - chosen[c] as f32;
+                        errors[0][x + 2][c] += error * 7.0 / 16.0;
+                        errors[1][x][c] += error * 3.0 / 16.0;
+                        errors[1][x + 1][c] += error * 5.0 / 16.0;
+                        errors[1][x + 2][c] += error * 1.0 / 16.0;
+                    }
+                }
+            }
+
+            errors.swap(0, 1);
+            errors[1].fill([0.0; 3]);
+        }
+
+        indices
+    }
+}
+
+/// Writes the frames as a lossless animated PNG, with transparency.
+pub fn write_apng(
+    scene: &Scene,
+    settings: &ExportSettings,
+    clip: &ClipSettings,
+    writer: impl Write,
+) -> Result<(), ExportError> {
+    let settings = clip.frame_settings(settings)?;
+    let frames = frames(scene, &settings)?;
+
+    let mut encoder = png::Encoder::new(writer, settings.width, settings.height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    // APNG counts plays like we do, but with zero for forever.
+    encoder.set_animated(frames.len() as u32, clip.plays.unwrap_or(0) as u32)?;
+
+    // The delay is in seconds, as a fraction, so a frame lasts 1 / fps.
+    let (numerator, denominator) = frame_rate_ratio(settings.fps);
+    let delay = match (u16::try_from(denominator), u16::try_from(numerator)) {
+        (Ok(delay_numerator), Ok(delay_denominator)) => (delay_numerator, delay_denominator),
+        _ => ((1000.0 / settings.fps).round().max(1.0) as u16, 1000),
+    };
+    encoder.set_frame_delay(delay.0, delay.1)?;
+
+    let mut writer = encoder.write_header()?;
+    for frame in frames {
+        writer.write_image_data(&frame.image.data)?;
+    }
+    writer.finish()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{builder::Builder, motion::Motion, scene::SceneBuilder, timing::Wait};
+
+    fn scene(length: Time) -> Scene {
+        let mut b = SceneBuilder::new();
+        b.play(Wait.with_duration(length));
+        b.finish()
+    }
+
+    #[test]
+    fn frame_times_include_the_end() {
+        let times = ExportSettings::default()
+            .with_fps(4.0)
+            .frame_times(&scene(1.0));
+        assert_eq!(times, vec![0.0, 0.25, 0.5, 0.75, 1.0]);
+    }
+
+    #[test]
+    fn frame_times_are_clamped_to_the_scene() {
+        let scene = scene(1.0);
+
+        let times = ExportSettings::default()
+            .with_fps(2.0)
+            .with_range(0.5, 3.0)
+            .frame_times(&scene);
+        assert_eq!(times, vec![0.5, 1.0]);
+
+        let times = ExportSettings::default()
+            .with_fps(2.0)
+            .with_range(2.0, 3.0)
+            .frame_times(&scene);
+        assert_eq!(times, vec![1.0]);
+    }
+
+    #[test]
+    fn frame_times_allow_for_rounding_errors() {
+        let times = ExportSettings::default()
+            .with_fps(30.0)
+            .frame_times(&scene(1.0));
+        assert_eq!(times.len(), 31);
+        assert_eq!(times.last(), Some(&1.0));
+
+        // A frame only fits if it is within the range.
+        let times = ExportSettings::default()
+            .with_fps(3.0)
+            .frame_times(&scene(0.9));
+        assert_eq!(times.len(), 3);
+    }
+
+    #[test]
+    fn scenes_without_duration_have_one_frame() {
+        let scene = SceneBuilder::new().finish();
+
+        assert_eq!(ExportSettings::default().frame_times(&scene), vec![0.0]);
+        assert_eq!(scene.time_to_alpha(0.0), 1.0);
+    }
+
+    #[test]
+    fn frame_rates_become_fractions() {
+        assert_eq!(frame_rate_ratio(30.0), (30, 1));
+        assert_eq!(frame_rate_ratio(29.97), (2997, 100));
+        assert_eq!(frame_rate_ratio(0.5), (1, 2));
+        assert_eq!(frame_rate_ratio(0.0001), (1, 10000));
+        assert_eq!(frame_rate_ratio(1e-12), (1, u32::MAX));
+    }
+
+    fn image(width: u32, height: u32, pixels: &[[u8; 4]]) -> Image {
+        Image {
+            width,
+            height,
+            data: pixels.concat(),
+        }
+    }
+
+    #[test]
+    fn colors_convert_to_limited_range() {
+        let mut planes = Vec::new();
+
+        let white = image(2, 2, &[[255, 255, 255, 255]; 4]);
+        rgba_to_yuv420(&white, &mut planes);
+        assert_eq!(planes, [235, 235, 235, 235, 128, 128]);
+
+        // Transparent pixels end up black, whatever their color.
+        let transparent = image(2, 2, &[[255, 0, 0, 0]; 4]);
+        rgba_to_yuv420(&transparent, &mut planes);
+        assert_eq!(planes, [16, 16, 16, 16, 128, 128]);
+    }
+
+    #[test]
+    fn chroma_is_averaged_over_odd_sizes() {
+        let red = [255, 0, 0, 255];
+        let blue = [0, 0, 255, 255];
+        let image = image(3, 1, &[red, blue, red]);
+
+        let mut planes = Vec::new();
+        rgba_to_yuv420(&image, &mut planes);
+
+        // Three luma samples, then U and V with two samples each, the first
+        // averaged over a red and a blue pixel and the second over red only.
+        assert_eq!(planes.len(), 3 + 2 * 2);
+        let (_, red_u, red_v) = yuv(&red);
+        let (_, blue_u, blue_v) = yuv(&blue);
+        assert_eq!(planes[3], ((red_u + blue_u) / 2.0).round() as u8);
+        assert_eq!(planes[4], red_u.round() as u8);
+        assert_eq!(planes[5], ((red_v + blue_v) / 2.0).round() as u8);
+        assert_eq!(planes[6], red_v.round() as u8);
+    }
+
+    #[test]
+    fn y4m_streams_start_with_a_header() {
+        let settings = ExportSettings::new(4, 2).with_fps(2.0);
+        let mut data = Vec::new();
+        write_y4m(&scene(0.5), &settings, &mut data).unwrap();
+
+        let header = b"YUV4MPEG2 W4 H2 F2:1 Ip A1:1 C420jpeg XCOLORRANGE=LIMITED\n";
+        assert!(data.starts_with(header));
+
+        // Two frames, each with 8 luma and 2 * 2 chroma samples.
+        let frame = [b"FRAME\n".as_slice(), &[16; 8], &[128; 4]].concat();
+        assert_eq!(data[header.len()..], [frame.clone(), frame].concat());
+    }
+
+    #[test]
+    fn clips_must_play_at_least_once() {
+        let settings = ExportSettings::new(4, 2);
+
+        assert!(ClipSettings::default().frame_settings(&settings).is_ok());
+        assert!(ClipSettings::default()
+            .with_plays(1)
+            .frame_settings(&settings)
+            .is_ok());
+        assert!(matches!(
+            ClipSettings::default()
+                .with_plays(0)
+                .frame_settings(&settings),
+            Err(ExportError::InvalidSettings(_))
+        ));
+    }
+
+    #[test]
+    fn gif_delays_keep_the_clip_at_speed() {
+        let delays: Vec<u16> = (0..6).map(|index| gif_delay(index, 30.0)).collect();
+        assert_eq!(delays, [3, 4, 3, 3, 4, 3]);
+        assert_eq!(delays.iter().sum::<u16>(), 20);
+
+        assert_eq!(gif_delay(0, 10.0), 10);
+        // Viewers slow down shorter delays anyway.
+        assert_eq!(gif_delay(0, 100.0), 2);
+    }
+
+    /// The color of a palette index.
+    fn color(palette: &GifPalette, index: u8) -> [u8; 3] {
+        palette.colors[index as usize * 3..][..3]
+            .try_into()
+            .unwrap()
+    }
+
+    #[test]
+    fn palettes_keep_an_index_for_transparency() {
+        let red = [255, 0, 0, 255];
+        let clear = [0, 0, 255, 0];
+
+        let opaque = GifPalette::new(&[image(16, 16, &[red; 256])]);
+        assert_eq!(opaque.transparent, None);
+        assert_eq!(opaque.colors.len(), 256 * 3);
+
+        let pixels: Vec<[u8; 4]> = (0..256)
+            .map(|i| if i % 2 == 0 { red } else { clear })
+            .collect();
+        let image = image(16, 16, &pixels);
+        let palette = GifPalette::new(std::slice::from_ref(&image));
+        let transparent = palette.transparent.expect("a transparent index");
+        assert_eq!(palette.colors.len(), 256 * 3);
+
+        let indices = palette.indices(&image, false);
+        assert_eq!(indices[1], transparent);
+        let [r, g, b] = color(&palette, indices[0]);
+        assert!(r > 240 && g < 16 && b < 16, "red became {:?}", [r, g, b]);
+    }
+
+    #[test]
+    fn dithering_mixes_colors_the_palette_lacks() {
+        let pixels: Vec<[u8; 4]> = (0..256)
+            .map(|i| {
+                if i % 2 == 0 {
+                    [255, 0, 0, 255]
+                } else {
+                    [0, 0, 255, 255]
+                }
+            })
+            .collect();
+        let palette = GifPalette::new(&[image(16, 16, &pixels)]);
+
+        // How far the average color of the pixels is from `wanted`.
+        let error = |indices: &[u8], wanted: [u8; 4]| {
+            (0..3)
+                .map(|c| {
+                    let sum: f32 = indices
+                        .iter()
+                        .map(|index| color(&palette, *index)[c] as f32)
+                        .sum();
+                    (sum / indices.len() as f32 - wanted[c] as f32).abs()
+                })
+                .sum::<f32>()
+        };
+
+        // Without dithering, a color the palette lacks snaps to its nearest
+        // one. With it, the pixels mix colors to average out closer to it.
+        for wanted in [[128, 0, 128, 255], [200, 0, 40, 255]] {
+            let image = image(16, 16, &[wanted; 256]);
+            let plain = palette.indices(&image, false);
+            let dithered = palette.indices(&image, true);
+
+            assert!(plain.iter().all(|index| *index == plain[0]));
+            assert!(dithered.iter().any(|index| *index != dithered[0]));
+            assert!(
+                error(&dithered, wanted) < error(&plain, wanted),
+                "dithering {wanted:?} doesn't get closer to it"
+            );
+        }
+    }
+}
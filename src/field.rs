@@ -0,0 +1,569 @@
+use egui::{pos2, Color32, Pos2, Vec2};
+use lyon::path::Path;
+use std::rc::Rc;
+
+use crate::{
+    animation::Animation,
+    builder::Builder,
+    component::{Component, Handle},
+    interpolation::Interpolatable,
+    motion::{Alpha, Motion},
+    object::{FillMaterial, Object, StrokeMaterial},
+    shapes::{animate_path, set_path, to_point, Arrow, ArrowHead},
+    timing::Concurrently,
+    world::World,
+};
+
+/// A vector field over the scene, in scene coordinates.
+pub type FieldFunction = Rc<dyn Fn(Pos2) -> Vec2>;
+
+/// Maps the magnitude of a vector to a color between `low` and `high`,
+/// reaching `high` at `max_magnitude`.
+#[derive(Clone, Copy, Debug)]
+pub struct ColorScale {
+    pub low: Color32,
+    pub high: Color32,
+    pub max_magnitude: f32,
+}
+
+impl ColorScale {
+    pub fn color(&self, magnitude: f32) -> Color32 {
+        let alpha = (magnitude / self.max_magnitude).clamp(0.0, 1.0);
+
+        self.low.interpolate(&self.high, alpha)
+    }
+}
+
+impl Default for ColorScale {
+    fn default() -> Self {
+        Self {
+            low: Color32::from_rgb(60, 120, 220),
+            high: Color32::from_rgb(250, 210, 60),
+            max_magnitude: 2.0,
+        }
+    }
+}
+
+/// Every multiple of `step` within `range`, or nothing if the step isn't
+/// positive.
+fn grid(x_range: (f32, f32), y_range: (f32, f32), step: f32) -> Vec<Pos2> {
+    if !(step > 0.0 && step.is_finite()) {
+        return Vec::new();
+    }
+
+    let steps = |(min, max): (f32, f32)| {
+        let first = (min / step).ceil() as i32;
+        let last = (max / step).floor() as i32;
+
+        (first..=last).map(move |i| i as f32 * step)
+    };
+
+    steps(y_range)
+        .flat_map(|y| steps(x_range).map(move |x| pos2(x, y)))
+        .collect()
+}
+
+/// A grid of arrows, each centered on its grid point, whose length and color
+/// follow the field there.
+pub struct VectorField {
+    f: FieldFunction,
+    x_range: (f32, f32),
+    y_range: (f32, f32),
+    step: f32,
+    length_scale: f32,
+    max_length: f32,
+    stroke_width: f32,
+    colors: ColorScale,
+}
+
+#[derive(Clone)]
+pub struct FieldArrow {
+    pub shaft: Handle<Object>,
+    pub head: Handle<Object>,
+    pub center: Pos2,
+    pub vector: Vec2,
+}
+
+#[derive(Clone)]
+pub struct VectorFieldHandle {
+    pub arrows: Vec<FieldArrow>,
+    head_size: f32,
+}
+
+impl VectorField {
+    pub fn new(
+        f: impl Fn(Pos2) -> Vec2 + 'static,
+        x_range: (f32, f32),
+        y_range: (f32, f32),
+    ) -> Self {
+        Self {
+            f: Rc::new(f),
+            x_range,
+            y_range,
+            step: 0.5,
+            length_scale: 0.5,
+            max_length: 0.45,
+            stroke_width: 0.03,
+            colors: ColorScale::default(),
+        }
+    }
+
+    /// The distance between neighbouring arrows. Panics unless it is
+    /// positive.
+    pub fn with_step(mut self, step: f32) -> Self {
+        assert!(step > 0.0, "vector field step must be positive, got {step}");
+        self.step = step;
+        self
+    }
+
+    /// How long an arrow is per unit of magnitude, up to `max_length`, so that
+    /// strong parts of the field don't run into their neighbours.
+    pub fn with_length(mut self, length_scale: f32, max_length: f32) -> Self {
+        self.length_scale = length_scale;
+        self.max_length = max_length;
+        self
+    }
+
+    pub fn with_stroke_width(mut self, stroke_width: f32) -> Self {
+        self.stroke_width = stroke_width;
+        self
+    }
+
+    pub fn with_colors(mut self, colors: ColorScale) -> Self {
+        self.colors = colors;
+        self
+    }
+}
+
+impl Component for VectorField {
+    type Handle = VectorFieldHandle;
+
+    fn build<B: Builder>(self, builder: &mut B) -> Self::Handle {
+        let head_size = self.stroke_width * 4.0;
+        let mut arrows = Vec::new();
+
+        for center in grid(self.x_range, self.y_range, self.step) {
+            let value = (self.f)(center);
+            let magnitude = value.length();
+
+            // Arrows for vanishing vectors have no direction to point in.
+            if !magnitude.is_finite() || magnitude < 1e-4 {
+                continue;
+            }
+
+            let vector = value / magnitude * (magnitude * self.length_scale).min(self.max_length);
+            let (shaft, heads) = VectorFieldHandle::build_paths(center, vector, head_size, 1.0);
+            let color = self.colors.color(magnitude);
+
+            arrows.push(FieldArrow {
+                shaft: builder.add(Object::new_model(
+                    shaft,
+                    StrokeMaterial::new(color, self.stroke_width).into(),
+                )),
+                head: builder.add(Object::new_model(heads, FillMaterial::new(color).into())),
+                center,
+                vector,
+            });
+        }
+
+        VectorFieldHandle { arrows, head_size }
+    }
+}
+
+impl VectorFieldHandle {
+    /// The paths of an arrow centered on `center`, scaled by `scale`.
+    fn build_paths(center: Pos2, vector: Vec2, head_size: f32, scale: f32) -> (Path, Path) {
+        if scale <= 0.0 {
+            return (Path::new(), Path::new());
+        }
+
+        let vector = vector * scale;
+        let (shaft, mut heads) = Arrow::build_paths(
+            center - vector / 2.0,
+            center + vector / 2.0,
+            ArrowHead::Triangle,
+            head_size * scale.min(1.0),
+            false,
+            0.0,
+        );
+
+        (shaft, heads.remove(0))
+    }
+
+    /// Grows every arrow out of its center at the same time.
+    pub fn grow(&self, duration: f32) -> Concurrently {
+        let head_size = self.head_size;
+        let mut animations = Concurrently::default();
+
+        for arrow in &self.arrows {
+            let (center, vector) = (arrow.center, arrow.vector);
+
+            animations.add(
+                animate_path(&arrow.shaft, move |alpha| {
+                    VectorFieldHandle::build_paths(center, vector, head_size, alpha).0
+                })
+                .with_duration(duration),
+            );
+            animations.add(
+                animate_path(&arrow.head, move |alpha| {
+                    VectorFieldHandle::build_paths(center, vector, head_size, alpha).1
+                })
+                .with_duration(duration),
+            );
+        }
+
+        animations
+    }
+}
+
+/// A polyline along with the distance from its start to each of its points.
+struct Polyline {
+    points: Vec<Pos2>,
+    distances: Vec<f32>,
+}
+
+impl Polyline {
+    fn new(points: Vec<Pos2>) -> Self {
+        let mut distances = vec![0.0];
+        for pair in points.windows(2) {
+            distances.push(distances.last().unwrap() + (pair[1] - pair[0]).length());
+        }
+
+        Self { points, distances }
+    }
+
+    fn length(&self) -> f32 {
+        *self.distances.last().unwrap()
+    }
+
+    fn point_at(&self, distance: f32) -> Pos2 {
+        let i = self
+            .distances
+            .partition_point(|&d| d <= distance)
+            .clamp(1, self.points.len() - 1);
+        let (d0, d1) = (self.distances[i - 1], self.distances[i]);
+        let alpha = if d1 > d0 {
+            (distance - d0) / (d1 - d0)
+        } else {
+            0.0
+        };
+
+        self.points[i - 1].interpolate(&self.points[i], alpha.clamp(0.0, 1.0))
+    }
+
+    /// Adds the part of the polyline between the two distances as a subpath.
+    fn add_range(&self, path_builder: &mut lyon::path::path::Builder, from: f32, to: f32) {
+        let (from, to) = (from.max(0.0), to.min(self.length()));
+        if from >= to {
+            return;
+        }
+
+        path_builder.begin(to_point(self.point_at(from)));
+        for (point, &distance) in self.points.iter().zip(&self.distances) {
+            if distance > from && distance < to {
+                path_builder.line_to(to_point(*point));
+            }
+        }
+        path_builder.line_to(to_point(self.point_at(to)));
+        path_builder.end(false);
+    }
+
+    fn range(&self, from: f32, to: f32) -> Path {
+        let mut path_builder = Path::builder();
+        self.add_range(&mut path_builder, from, to);
+
+        path_builder.build()
+    }
+}
+
+/// Lines following the field from a grid of seed points, traced with RK4 in
+/// steps of equal length.
+pub struct StreamLines {
+    f: FieldFunction,
+    x_range: (f32, f32),
+    y_range: (f32, f32),
+    seeds: Vec<Pos2>,
+    step_size: f32,
+    max_length: f32,
+    stroke_width: f32,
+    colors: ColorScale,
+}
+
+#[derive(Clone)]
+pub struct StreamLine {
+    pub line: Handle<Object>,
+    polyline: Rc<Polyline>,
+}
+
+#[derive(Clone)]
+pub struct StreamLinesHandle {
+    pub lines: Vec<StreamLine>,
+}
+
+impl StreamLines {
+    /// Stream lines seeded every unit within the ranges, which also bound the
+    /// lines.
+    pub fn new(
+        f: impl Fn(Pos2) -> Vec2 + 'static,
+        x_range: (f32, f32),
+        y_range: (f32, f32),
+    ) -> Self {
+        Self {
+            f: Rc::new(f),
+            x_range,
+            y_range,
+            seeds: grid(x_range, y_range, 1.0),
+            step_size: 0.05,
+            max_length: 6.0,
+            stroke_width: 0.03,
+            colors: ColorScale::default(),
+        }
+    }
+
+    /// Panics unless the spacing is positive.
+    pub fn with_seed_spacing(mut self, spacing: f32) -> Self {
+        assert!(
+            spacing > 0.0,
+            "seed spacing must be positive, got {spacing}"
+        );
+        self.seeds = grid(self.x_range, self.y_range, spacing);
+        self
+    }
+
+    pub fn with_seeds(mut self, seeds: Vec<Pos2>) -> Self {
+        self.seeds = seeds;
+        self
+    }
+
+    /// The length of each integration step, and how long a line can get.
+    /// Panics unless the step size is positive.
+    pub fn with_integration(mut self, step_size: f32, max_length: f32) -> Self {
+        assert!(
+            step_size > 0.0,
+            "integration step size must be positive, got {step_size}"
+        );
+        self.step_size = step_size;
+        self.max_length = max_length;
+        self
+    }
+
+    pub fn with_stroke_width(mut self, stroke_width: f32) -> Self {
+        self.stroke_width = stroke_width;
+        self
+    }
+
+    /// Colors each line by the average magnitude of the field along it.
+    pub fn with_colors(mut self, colors: ColorScale) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    /// The field's direction at `point`, or `None` where it vanishes.
+    fn direction(&self, point: Pos2) -> Option<Vec2> {
+        let value = (self.f)(point);
+        let magnitude = value.length();
+
+        (magnitude.is_finite() && magnitude > 1e-4).then(|| value / magnitude)
+    }
+
+    fn in_bounds(&self, point: Pos2) -> bool {
+        (self.x_range.0..=self.x_range.1).contains(&point.x)
+            && (self.y_range.0..=self.y_range.1).contains(&point.y)
+    }
+
+    fn rk4_step(&self, point: Pos2) -> Option<Pos2> {
+        let h = self.step_size;
+        let k1 = self.direction(point)?;
+        let k2 = self.direction(point + k1 * h / 2.0)?;
+        let k3 = self.direction(point + k2 * h / 2.0)?;
+        let k4 = self.direction(point + k3 * h)?;
+
+        Some(point + (k1 + k2 * 2.0 + k3 * 2.0 + k4) * h / 6.0)
+    }
+
+    fn trace(&self, seed: Pos2) -> Vec<Pos2> {
+        let mut points = vec![seed];
+        let steps = (self.max_length / self.step_size) as usize;
+
+        for _ in 0..steps {
+            match self.rk4_step(*points.last().unwrap()) {
+                Some(next) if self.in_bounds(next) => points.push(next),
+                _ => break,
+            }
+        }
+
+        points
+    }
+}
+
+impl Component for StreamLines {
+    type Handle = StreamLinesHandle;
+
+    fn build<B: Builder>(self, builder: &mut B) -> Self::Handle {
+        let mut lines = Vec::new();
+
+        for &seed in &self.seeds {
+            let points = self.trace(seed);
+            if points.len() < 2 {
+                continue;
+            }
+
+            let magnitude = points
+                .iter()
+                .map(|point| (self.f)(*point).length())
+                .sum::<f32>()
+                / points.len() as f32;
+
+            let polyline = Polyline::new(points);
+            let line = builder.add(Object::new_model(
+                polyline.range(0.0, polyline.length()),
+                StrokeMaterial::new(self.colors.color(magnitude), self.stroke_width).into(),
+            ));
+
+            lines.push(StreamLine {
+                line,
+                polyline: Rc::new(polyline),
+            });
+        }
+
+        StreamLinesHandle { lines }
+    }
+}
+
+/// Dashes running along a stream line. The dash positions only depend on the
+/// alpha, so any frame can be rendered on its own.
+struct Flow {
+    line: StreamLine,
+    duration: f32,
+    speed: f32,
+    dash_length: f32,
+    gap_length: f32,
+    /// Where along the dash pattern the line starts, so that neighbouring
+    /// lines don't move in lockstep.
+    phase: f32,
+}
+
+impl Motion for Flow {
+    fn animate(&self, world: &mut World, alpha: Alpha) {
+        let polyline = &self.line.polyline;
+
+        // Leave the line whole once the flow is over.
+        if alpha >= 1.0 {
+            set_path(
+                world,
+                *self.line.line,
+                polyline.range(0.0, polyline.length()),
+            );
+            return;
+        }
+
+        let period = self.dash_length + self.gap_length;
+        let offset = (self.phase + alpha * self.duration * self.speed).rem_euclid(period);
+
+        let mut path_builder = Path::builder();
+        let mut start = offset - period;
+        while start < polyline.length() {
+            polyline.add_range(&mut path_builder, start, start + self.dash_length);
+            start += period;
+        }
+
+        set_path(world, *self.line.line, path_builder.build());
+    }
+}
+
+impl Animation for Flow {
+    fn duration(&self) -> f32 {
+        self.duration
+    }
+}
+
+impl StreamLinesHandle {
+    /// Draws every line from its seed at the same time.
+    pub fn create(&self, duration: f32) -> Concurrently {
+        let mut animations = Concurrently::default();
+
+        for line in &self.lines {
+            let polyline = line.polyline.clone();
+            animations.add(
+                animate_path(&line.line, move |alpha| {
+                    polyline.range(0.0, polyline.length() * alpha)
+                })
+                .with_duration(duration),
+            );
+        }
+
+        animations
+    }
+
+    /// Breaks the lines into dashes that flow along the field at `speed` scene
+    /// units per second.
+    pub fn flow(&self, duration: f32, speed: f32) -> Concurrently {
+        let (dash_length, gap_length) = (0.3, 0.2);
+
+        self.lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                Box::new(Flow {
+                    line: line.clone(),
+                    duration,
+                    speed,
+                    dash_length,
+                    gap_length,
+                    // The golden ratio spreads the phases out evenly.
+                    phase: (i as f32 * 0.618_034).fract() * (dash_length + gap_length),
+                }) as _
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::vec2;
+
+    #[test]
+    fn grid_covers_the_ranges() {
+        assert_eq!(
+            grid((-1.0, 1.0), (0.0, 0.5), 1.0),
+            [pos2(-1.0, 0.0), pos2(0.0, 0.0), pos2(1.0, 0.0)]
+        );
+        assert_eq!(grid((0.0, 1.0), (0.0, 1.0), 0.5).len(), 9);
+    }
+
+    #[test]
+    fn grid_without_a_positive_step_is_empty() {
+        for step in [0.0, -0.5, f32::NAN] {
+            assert!(
+                grid((-1.0, 1.0), (-1.0, 1.0), step).is_empty(),
+                "step {step}"
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "vector field step must be positive")]
+    fn vector_field_rejects_a_zero_step() {
+        VectorField::new(|_| vec2(1.0, 0.0), (-1.0, 1.0), (-1.0, 1.0)).with_step(0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "integration step size must be positive")]
+    fn stream_lines_reject_a_zero_step_size() {
+        StreamLines::new(|_| vec2(1.0, 0.0), (-1.0, 1.0), (-1.0, 1.0)).with_integration(0.0, 1.0);
+    }
+
+    #[test]
+    fn stream_lines_stop_at_their_length_or_the_bounds() {
+        let lines = StreamLines::new(|_| vec2(1.0, 0.0), (-10.0, 10.0), (-1.0, 1.0))
+            .with_integration(0.1, 2.0);
+
+        let long = Polyline::new(lines.trace(pos2(0.0, 0.0)));
+        assert!((long.length() - 2.0).abs() < 1e-3);
+
+        let short = Polyline::new(lines.trace(pos2(9.5, 0.0)));
+        assert!(short.length() <= 0.5);
+        assert!(lines.in_bounds(*short.points.last().unwrap()));
+    }
+}
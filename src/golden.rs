@@ -0,0 +1,422 @@
+use egui::Color32;
+use std::{
+    env, fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    rasterizer::{Image, Rasterizer},
+    scene::Scene,
+    timing::Time,
+};
+
+/// Set to anything but `0` to write the references from what is rendered
+/// instead of comparing against them, e.g., after a change that is meant to
+/// change how scenes look: `ENIMATE_BLESS=1 cargo test`.
+pub const BLESS_VAR: &str = "ENIMATE_BLESS";
+
+fn blessing() -> bool {
+    env::var_os(BLESS_VAR).is_some_and(|value| !value.is_empty() && value != "0")
+}
+
+#[derive(Debug)]
+pub enum GoldenError {
+    Io(io::Error),
+    Encoding(png::EncodingError),
+    Decoding(png::DecodingError),
+    MissingReference(PathBuf),
+    SizeMismatch {
+        reference: PathBuf,
+        expected: (u32, u32),
+        actual: (u32, u32),
+    },
+    Mismatch {
+        reference: PathBuf,
+        differing: usize,
+        total: usize,
+        actual: PathBuf,
+        diff: PathBuf,
+    },
+}
+
+impl fmt::Display for GoldenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GoldenError::Io(error) => write!(f, "i/o error: {error}"),
+            GoldenError::Encoding(error) => write!(f, "png encoding failed: {error}"),
+            GoldenError::Decoding(error) => write!(f, "png decoding failed: {error}"),
+            GoldenError::MissingReference(reference) => write!(
+                f,
+                "there is no reference at {}, run with {BLESS_VAR}=1 to write it",
+                reference.display()
+            ),
+            GoldenError::SizeMismatch {
+                reference,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{} is {}x{}, but the frame is {}x{}",
+                reference.display(),
+                expected.0,
+                expected.1,
+                actual.0,
+                actual.1
+            ),
+            GoldenError::Mismatch {
+                reference,
+                differing,
+                total,
+                actual,
+                diff,
+            } => write!(
+                f,
+                "{differing} of {total} pixels differ from {}, see {} and {}, \
+                 or run with {BLESS_VAR}=1 if the change is intended",
+                reference.display(),
+                actual.display(),
+                diff.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GoldenError {}
+
+impl From<io::Error> for GoldenError {
+    fn from(error: io::Error) -> Self {
+        GoldenError::Io(error)
+    }
+}
+
+impl From<png::EncodingError> for GoldenError {
+    fn from(error: png::EncodingError) -> Self {
+        GoldenError::Encoding(error)
+    }
+}
+
+impl From<png::DecodingError> for GoldenError {
+    fn from(error: png::DecodingError) -> Self {
+        GoldenError::Decoding(error)
+    }
+}
+
+/// How different a frame may be from its reference.
+#[derive(Clone, Copy, Debug)]
+pub struct Tolerance {
+    /// How different two pixels may look before they count as differing,
+    /// from 0 for identical to 1 for black and white.
+    pub threshold: f32,
+    /// The fraction of pixels that may differ, e.g., along anti-aliased
+    /// edges.
+    pub max_differing: f32,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self {
+            threshold: 0.1,
+            max_differing: 0.001,
+        }
+    }
+}
+
+/// The YIQ color of a pixel, composited onto white. YIQ separates brightness
+/// from color, which the eye is much more sensitive to.
+fn yiq(pixel: &[u8]) -> [f32; 3] {
+    let alpha = pixel[3] as f32 / 255.0;
+    let [r, g, b] = [0, 1, 2].map(|i| 255.0 + (pixel[i] as f32 - 255.0) * alpha);
+
+    [
+        r * 0.298_895_3 + g * 0.586_622_5 + b * 0.114_482_23,
+        r * 0.595_977_99 - g * 0.274_176_1 - b * 0.321_801_9,
+        r * 0.211_470_17 - g * 0.522_617_1 + b * 0.311_146_94,
+    ]
+}
+
+/// How different two pixels look, from 0 for identical to 1 for black and
+/// white, as in "Measuring perceived color difference using YIQ NTSC
+/// transmission color space in mobile applications" by Kotsarenko and Ramos.
+fn perceptual_difference(a: &[u8], b: &[u8]) -> f32 {
+    // The difference between black and white.
+    const MAX: f32 = 35215.0;
+
+    let ([y1, i1, q1], [y2, i2, q2]) = (yiq(a), yiq(b));
+    let (y, i, q) = (y1 - y2, i1 - i2, q1 - q2);
+
+    ((0.5053 * y * y + 0.299 * i * i + 0.1957 * q * q) / MAX).sqrt()
+}
+
+pub struct Comparison {
+    pub differing: usize,
+    /// The reference, faded, with the pixels that differ in red.
+    pub diff: Image,
+}
+
+/// Compares two images of the same size pixel by pixel.
+pub fn compare(expected: &Image, actual: &Image, tolerance: &Tolerance) -> Comparison {
+    let mut differing = 0;
+    let data = expected
+        .data
+        .chunks_exact(4)
+        .zip(actual.data.chunks_exact(4))
+        .flat_map(|(expected, actual)| {
+            if perceptual_difference(expected, actual) > tolerance.threshold {
+                differing += 1;
+                [255, 0, 0, 255]
+            } else {
+                let faded = (255.0 - (255.0 - yiq(expected)[0]) * 0.1) as u8;
+                [faded, faded, faded, 255]
+            }
+        })
+        .collect();
+
+    Comparison {
+        differing,
+        diff: Image {
+            width: expected.width,
+            height: expected.height,
+            data,
+        },
+    }
+}
+
+/// Renders scenes and compares them against reference PNGs in a directory,
+/// to catch changes to how things look. When a frame doesn't match, what was
+/// rendered and a diff are written to a `failures` directory next to the
+/// references.
+///
+/// ```ignore
+/// #[test]
+/// fn shapes() {
+///     Golden::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden"))
+///         .assert_matches("shapes", &shapes(), 1.0);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Golden {
+    directory: PathBuf,
+    width: u32,
+    height: u32,
+    background: Color32,
+    tolerance: Tolerance,
+}
+
+impl Golden {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+            width: 320,
+            height: 180,
+            background: Color32::BLACK,
+            tolerance: Tolerance::default(),
+        }
+    }
+
+    pub fn with_size(mut self, width: u32, height: u32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    pub fn with_background(mut self, background: Color32) -> Self {
+        self.background = background;
+        self
+    }
+
+    pub fn with_tolerance(mut self, tolerance: Tolerance) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn render(&self, scene: &Scene, time: Time) -> Image {
+        let snapshot = scene.render_at(time, (self.width as f32, self.height as f32));
+        Rasterizer::new(self.width, self.height)
+            .with_background(self.background)
+            .render(&snapshot)
+    }
+
+    /// Compares the scene at `time` against the reference `name`, or writes
+    /// the reference when blessing.
+    pub fn check(&self, name: &str, scene: &Scene, time: Time) -> Result<(), GoldenError> {
+        self.check_or_bless(name, scene, time, blessing())
+    }
+
+    fn check_or_bless(
+        &self,
+        name: &str,
+        scene: &Scene,
+        time: Time,
+        bless: bool,
+    ) -> Result<(), GoldenError> {
+        let actual = self.render(scene, time);
+        let reference = self.directory.join(format!("{name}.png"));
+
+        if bless {
+            fs::create_dir_all(&self.directory)?;
+            actual.save_png(&reference)?;
+            return Ok(());
+        }
+
+        if !reference.exists() {
+            return Err(GoldenError::MissingReference(reference));
+        }
+
+        let expected = Image::load_png(&reference)?;
+        let failures = self.directory.join("failures");
+        let actual_path = failures.join(format!("{name}.actual.png"));
+        let diff_path = failures.join(format!("{name}.diff.png"));
+
+        if (expected.width, expected.height) != (actual.width, actual.height) {
+            fs::create_dir_all(&failures)?;
+            actual.save_png(&actual_path)?;
+            return Err(GoldenError::SizeMismatch {
+                reference,
+                expected: (expected.width, expected.height),
+                actual: (actual.width, actual.height),
+            });
+        }
+
+        let comparison = compare(&expected, &actual, &self.tolerance);
+        let total = (actual.width * actual.height) as usize;
+
+        if comparison.differing as f32 > self.tolerance.max_differing * total as f32 {
+            fs::create_dir_all(&failures)?;
+            actual.save_png(&actual_path)?;
+            comparison.diff.save_png(&diff_path)?;
+            return Err(GoldenError::Mismatch {
+                reference,
+                differing: comparison.differing,
+                total,
+                actual: actual_path,
+                diff: diff_path,
+            });
+        }
+
+        // Don't leave the images of an earlier failure around once it's fixed.
+        remove_if_exists(&actual_path)?;
+        remove_if_exists(&diff_path)?;
+
+        Ok(())
+    }
+
+    /// Like [`Golden::check`], but panics if the frame doesn't match.
+    #[track_caller]
+    pub fn assert_matches(&self, name: &str, scene: &Scene, time: Time) {
+        if let Err(error) = self.check(name, scene, time) {
+            panic!("golden image {name:?} at {time}s: {error}");
+        }
+    }
+}
+
+fn remove_if_exists(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        builder::Builder, component::ComponentExt, object::FillMaterial, scene::SceneBuilder,
+        shapes::Circle,
+    };
+    use egui::pos2;
+
+    /// A directory of references of its own for each test, since they run
+    /// in parallel.
+    fn directory(test: &str) -> PathBuf {
+        let directory =
+            env::temp_dir().join(format!("enimate-golden-{}-{test}", std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        directory
+    }
+
+    fn circle_at(x: f32, color: Color32) -> Scene {
+        let mut b = SceneBuilder::new();
+        b.add(
+            Circle {
+                radius: 1.5,
+                material: FillMaterial::new(color).into(),
+            }
+            .with_position(pos2(x, 0.0)),
+        );
+        b.finish()
+    }
+
+    #[test]
+    fn different_frames_fail_with_a_diff() {
+        let directory = directory("different");
+        let golden = Golden::new(&directory);
+        golden
+            .check_or_bless("circle", &circle_at(0.0, Color32::RED), 0.0, true)
+            .unwrap();
+
+        let error = golden
+            .check_or_bless("circle", &circle_at(2.0, Color32::BLUE), 0.0, false)
+            .unwrap_err();
+        let GoldenError::Mismatch {
+            differing,
+            total,
+            actual,
+            diff,
+            ..
+        } = error
+        else {
+            panic!("expected a mismatch, got {error}");
+        };
+        assert!(differing > total / 100, "{differing} of {total}");
+        assert_eq!(diff, directory.join("failures/circle.diff.png"));
+        assert!(diff.exists() && actual.exists());
+
+        // Once the frame matches again, the failure is cleaned up.
+        golden
+            .check_or_bless("circle", &circle_at(0.0, Color32::RED), 0.0, false)
+            .unwrap();
+        assert!(!diff.exists() && !actual.exists());
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn edges_shifted_by_part_of_a_pixel_are_within_the_default_tolerance() {
+        let directory = directory("anti-aliasing");
+        let golden = Golden::new(&directory);
+        // A tenth of a pixel, at 22.5 pixels per unit, which changes how much
+        // of each edge pixel is covered like a different anti-aliasing would.
+        let shifted = circle_at(0.1 / 22.5, Color32::RED);
+        golden
+            .check_or_bless("circle", &circle_at(0.0, Color32::RED), 0.0, true)
+            .unwrap();
+
+        let exact = Tolerance {
+            threshold: 0.0,
+            max_differing: 0.0,
+        };
+        let expected = Image::load_png(directory.join("circle.png")).unwrap();
+        let actual = golden.render(&shifted, 0.0);
+        assert!(compare(&expected, &actual, &exact).differing > 0);
+
+        golden
+            .check_or_bless("circle", &shifted, 0.0, false)
+            .unwrap();
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn missing_references_are_reported() {
+        let directory = directory("missing");
+        let error = Golden::new(&directory)
+            .check_or_bless("circle", &circle_at(0.0, Color32::RED), 0.0, false)
+            .unwrap_err();
+
+        assert!(
+            matches!(&error, GoldenError::MissingReference(reference) if *reference == directory.join("circle.png")),
+            "{error}"
+        );
+        assert!(!directory.exists());
+    }
+}
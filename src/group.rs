@@ -3,6 +3,7 @@ use std::ops::{Deref, DerefMut};
 use crate::{
     builder::Builder,
     component::{Component, Handle},
+    dynamics::DynamicTransform,
     object::Transform,
 };
 
@@ -11,6 +12,12 @@ pub struct Group<C: Component> {
     transform: Transform,
 }
 
+impl<C: Component> Default for Group<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<C: Component> Group<C> {
     pub fn new() -> Self {
         Self {
@@ -75,4 +82,8 @@ impl<C: Component> Component for Group<C> {
 
         GroupHandle { children }
     }
+
+    fn transform(&self) -> DynamicTransform {
+        self.transform.into()
+    }
 }
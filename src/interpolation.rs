@@ -0,0 +1,310 @@
+use egui::{Color32, Pos2};
+use lyon::{
+    geom::{CubicBezierSegment, QuadraticBezierSegment},
+    math::Point,
+    path::{Event, Path},
+};
+
+use crate::object::Transform;
+
+/// A value that can be blended with another value of the same type. An alpha
+/// of 0 returns `self` and an alpha of 1 returns `other`.
+pub trait Interpolatable {
+    fn interpolate(&self, other: &Self, alpha: f32) -> Self;
+}
+
+impl Interpolatable for f32 {
+    fn interpolate(&self, other: &Self, alpha: f32) -> Self {
+        self + (other - self) * alpha
+    }
+}
+
+impl Interpolatable for Pos2 {
+    fn interpolate(&self, other: &Self, alpha: f32) -> Self {
+        *self + (*other - *self) * alpha
+    }
+}
+
+impl Interpolatable for Color32 {
+    fn interpolate(&self, other: &Self, alpha: f32) -> Self {
+        let channel = |a: u8, b: u8| (a as f32).interpolate(&(b as f32), alpha).round() as u8;
+
+        Color32::from_rgba_premultiplied(
+            channel(self.r(), other.r()),
+            channel(self.g(), other.g()),
+            channel(self.b(), other.b()),
+            channel(self.a(), other.a()),
+        )
+    }
+}
+
+impl Interpolatable for Transform {
+    fn interpolate(&self, other: &Self, alpha: f32) -> Self {
+        Transform {
+            position: self.position.interpolate(&other.position, alpha),
+            rotation: self.rotation.interpolate(&other.rotation, alpha),
+            scale: self.scale.interpolate(&other.scale, alpha),
+            anchor: self.anchor.interpolate(&other.anchor, alpha),
+        }
+    }
+}
+
+/// Paths are morphed by converting every segment to a cubic bezier, padding
+/// the path with fewer subpaths with degenerate ones, and subdividing the
+/// subpaths with fewer segments until both sides line up one-to-one.
+impl Interpolatable for Path {
+    fn interpolate(&self, other: &Self, alpha: f32) -> Self {
+        let mut from = subpaths(self);
+        let mut to = subpaths(other);
+
+        pad_subpaths(&mut from, &to);
+        pad_subpaths(&mut to, &from);
+
+        let mut builder = Path::builder();
+
+        for (mut from, mut to) in from.into_iter().zip(to) {
+            equalize_segments(&mut from.segments, to.segments.len());
+            equalize_segments(&mut to.segments, from.segments.len());
+
+            let lerp = |a: Point, b: Point| a.lerp(b, alpha);
+
+            builder.begin(lerp(from.segments[0].from, to.segments[0].from));
+            for (a, b) in from.segments.iter().zip(&to.segments) {
+                builder.cubic_bezier_to(
+                    lerp(a.ctrl1, b.ctrl1),
+                    lerp(a.ctrl2, b.ctrl2),
+                    lerp(a.to, b.to),
+                );
+            }
+            builder.end(if alpha < 0.5 { from.closed } else { to.closed });
+        }
+
+        builder.build()
+    }
+}
+
+struct SubPath {
+    segments: Vec<CubicBezierSegment<f32>>,
+    closed: bool,
+}
+
+impl SubPath {
+    fn degenerate(at: Point) -> Self {
+        Self {
+            segments: vec![CubicBezierSegment {
+                from: at,
+                ctrl1: at,
+                ctrl2: at,
+                to: at,
+            }],
+            closed: true,
+        }
+    }
+
+    fn center(&self) -> Point {
+        let sum = self
+            .segments
+            .iter()
+            .fold(Point::zero(), |acc, segment| acc + segment.from.to_vector());
+
+        sum / self.segments.len() as f32
+    }
+}
+
+fn line_to_cubic(from: Point, to: Point) -> CubicBezierSegment<f32> {
+    CubicBezierSegment {
+        from,
+        ctrl1: from.lerp(to, 1.0 / 3.0),
+        ctrl2: from.lerp(to, 2.0 / 3.0),
+        to,
+    }
+}
+
+fn subpaths(path: &Path) -> Vec<SubPath> {
+    let mut subpaths = Vec::new();
+    let mut segments = Vec::new();
+
+    for event in path.iter() {
+        match event {
+            Event::Begin { .. } => segments = Vec::new(),
+            Event::Line { from, to } => segments.push(line_to_cubic(from, to)),
+            Event::Quadratic { from, ctrl, to } => {
+                segments.push(QuadraticBezierSegment { from, ctrl, to }.to_cubic())
+            }
+            Event::Cubic {
+                from,
+                ctrl1,
+                ctrl2,
+                to,
+            } => segments.push(CubicBezierSegment {
+                from,
+                ctrl1,
+                ctrl2,
+                to,
+            }),
+            Event::End { last, first, close } => {
+                if close && last != first {
+                    segments.push(line_to_cubic(last, first));
+                }
+                if segments.is_empty() {
+                    segments.push(line_to_cubic(first, first));
+                }
+
+                subpaths.push(SubPath {
+                    segments: std::mem::take(&mut segments),
+                    closed: close,
+                });
+            }
+        }
+    }
+
+    subpaths
+}
+
+/// Pads `subpaths` with degenerate subpaths placed at the center of their
+/// counterpart in `other`, so that missing contours grow out of nothing.
+fn pad_subpaths(subpaths: &mut Vec<SubPath>, other: &[SubPath]) {
+    while subpaths.len() < other.len() {
+        let center = other[subpaths.len()].center();
+        subpaths.push(SubPath::degenerate(center));
+    }
+}
+
+/// Splits the longest segment in half until there are `count` segments.
+fn equalize_segments(segments: &mut Vec<CubicBezierSegment<f32>>, count: usize) {
+    while segments.len() < count {
+        let (longest, _) = segments
+            .iter()
+            .enumerate()
+            .map(|(i, segment)| (i, (segment.to - segment.from).length()))
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .unwrap();
+
+        let (first, second) = segments[longest].split(0.5);
+        segments[longest] = first;
+        segments.insert(longest + 1, second);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lyon::{algorithms::aabb::bounding_box, math::point};
+
+    fn square(half: f32, close: bool) -> Path {
+        let mut builder = Path::builder();
+        builder.begin(point(-half, -half));
+        builder.line_to(point(half, -half));
+        builder.line_to(point(half, half));
+        builder.line_to(point(-half, half));
+        builder.end(close);
+        builder.build()
+    }
+
+    fn triangle() -> Path {
+        let mut builder = Path::builder();
+        builder.begin(point(0.0, -1.0));
+        builder.line_to(point(1.0, 1.0));
+        builder.line_to(point(-1.0, 1.0));
+        builder.close();
+        builder.build()
+    }
+
+    fn subpath_count(path: &Path) -> usize {
+        path.iter()
+            .filter(|event| matches!(event, Event::Begin { .. }))
+            .count()
+    }
+
+    #[test]
+    fn path_interpolation_starts_and_ends_at_the_paths() {
+        let from = square(1.0, true);
+        let to = triangle();
+
+        assert_eq!(
+            bounding_box(&from.interpolate(&to, 0.0)),
+            bounding_box(&from)
+        );
+        assert_eq!(bounding_box(&from.interpolate(&to, 1.0)), bounding_box(&to));
+    }
+
+    #[test]
+    fn path_interpolation_blends_points() {
+        let halfway = square(1.0, true).interpolate(&square(2.0, true), 0.5);
+        let bounds = bounding_box(&halfway);
+
+        assert_eq!(bounds.min, point(-1.5, -1.5));
+        assert_eq!(bounds.max, point(1.5, 1.5));
+    }
+
+    #[test]
+    fn path_interpolation_switches_closing_halfway() {
+        let from = square(1.0, true);
+        let to = square(1.0, false);
+        let closed = |path: Path| {
+            path.iter()
+                .any(|event| matches!(event, Event::End { close: true, .. }))
+        };
+
+        assert!(closed(from.interpolate(&to, 0.25)));
+        assert!(!closed(from.interpolate(&to, 0.75)));
+    }
+
+    #[test]
+    fn missing_subpaths_grow_from_the_center_of_their_counterpart() {
+        let one = square(1.0, true);
+
+        let mut builder = Path::builder();
+        for event in one.iter() {
+            builder.path_event(event);
+        }
+        builder.begin(point(4.0, 4.0));
+        builder.line_to(point(6.0, 4.0));
+        builder.line_to(point(6.0, 6.0));
+        builder.close();
+        let two = builder.build();
+
+        assert_eq!(subpath_count(&one.interpolate(&two, 0.0)), 2);
+        assert_eq!(subpath_count(&two.interpolate(&one, 1.0)), 2);
+
+        // The missing subpath starts out as a point at the center of the
+        // corners of the one it grows into.
+        let grown = subpaths(&one.interpolate(&two, 0.0)).pop().unwrap();
+        let center = point(16.0 / 3.0, 14.0 / 3.0);
+        for segment in grown.segments {
+            assert!((segment.from - center).length() < 1e-5);
+            assert!((segment.to - center).length() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn equalize_segments_splits_the_longest_segment() {
+        let mut segments = vec![
+            line_to_cubic(point(0.0, 0.0), point(1.0, 0.0)),
+            line_to_cubic(point(1.0, 0.0), point(5.0, 0.0)),
+        ];
+
+        equalize_segments(&mut segments, 3);
+
+        let ends: Vec<_> = segments.iter().map(|s| (s.from.x, s.to.x)).collect();
+        assert_eq!(ends, [(0.0, 1.0), (1.0, 3.0), (3.0, 5.0)]);
+    }
+
+    #[test]
+    fn equalize_segments_keeps_segments_when_there_are_enough() {
+        let mut segments = vec![line_to_cubic(point(0.0, 0.0), point(1.0, 0.0)); 3];
+        equalize_segments(&mut segments, 2);
+        assert_eq!(segments.len(), 3);
+    }
+
+    #[test]
+    fn equalize_segments_handles_nan_lengths() {
+        let mut segments = vec![
+            line_to_cubic(point(0.0, 0.0), point(f32::NAN, 0.0)),
+            line_to_cubic(point(0.0, 0.0), point(1.0, 0.0)),
+        ];
+
+        equalize_segments(&mut segments, 4);
+        assert_eq!(segments.len(), 4);
+    }
+}
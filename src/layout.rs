@@ -0,0 +1,422 @@
+use egui::{pos2, vec2, Pos2, Rect, Vec2};
+use std::rc::Rc;
+
+use crate::{
+    component::{Component, Handle},
+    dynamics::{Dynamic, OwnedDynamic},
+    group::GroupHandle,
+    motion::AddUpdater,
+    object::ObjectId,
+    trigger::Trigger,
+    world::World,
+};
+
+// Scene coordinates are y-down, so up is negative.
+pub const UP: Vec2 = vec2(0.0, -1.0);
+pub const DOWN: Vec2 = vec2(0.0, 1.0);
+pub const LEFT: Vec2 = vec2(-1.0, 0.0);
+pub const RIGHT: Vec2 = vec2(1.0, 0.0);
+pub const UP_LEFT: Vec2 = vec2(-1.0, -1.0);
+pub const UP_RIGHT: Vec2 = vec2(1.0, -1.0);
+pub const DOWN_LEFT: Vec2 = vec2(-1.0, 1.0);
+pub const DOWN_RIGHT: Vec2 = vec2(1.0, 1.0);
+
+/// The point on the edge of `rect` in `direction`, e.g., the middle of the
+/// right edge for [`RIGHT`] or the top left corner for [`UP_LEFT`].
+pub fn edge(rect: Rect, direction: Vec2) -> Pos2 {
+    rect.center() + rect.size() / 2.0 * direction
+}
+
+/// Keeps only the components of `delta` along which `direction` points.
+fn along(delta: Vec2, direction: Vec2) -> Vec2 {
+    vec2(
+        if direction.x != 0.0 { delta.x } else { 0.0 },
+        if direction.y != 0.0 { delta.y } else { 0.0 },
+    )
+}
+
+/// How far `bounds` has to move to be `buff` away from `target` in
+/// `direction`, centered on it along the other axis.
+pub fn next_to(bounds: Rect, target: Rect, direction: Vec2, buff: f32) -> Vec2 {
+    edge(target, direction) + direction * buff - edge(bounds, -direction)
+}
+
+/// How far `bounds` has to move for its edge in `direction` to line up with
+/// the same edge of `target`.
+pub fn align_to(bounds: Rect, target: Rect, direction: Vec2) -> Vec2 {
+    along(edge(target, direction) - edge(bounds, direction), direction)
+}
+
+/// How far `bounds` has to move to be `buff` away from the edge of `frame` in
+/// `direction`, or from its corner for a diagonal direction.
+pub fn to_edge(bounds: Rect, frame: Rect, direction: Vec2, buff: f32) -> Vec2 {
+    along(
+        edge(frame, direction) - direction * buff - edge(bounds, direction),
+        direction,
+    )
+}
+
+/// How far each of `bounds` has to move to be lined up one after the other in
+/// `direction` (one of [`UP`], [`DOWN`], [`LEFT`] or [`RIGHT`]), `buff` apart.
+/// Along the other axis, they are aligned on the edge `align` points to, or on
+/// their centers if it is zero. The row stays centered where the bounds were.
+pub fn arrange(bounds: &[Rect], direction: Vec2, buff: f32, align: Vec2) -> Vec<Vec2> {
+    let Some(&first) = bounds.first() else {
+        return Vec::new();
+    };
+
+    // The axis the items are lined up on, and the one they are aligned on.
+    let perpendicular = vec2(direction.y, direction.x);
+    let align = along(align, perpendicular);
+
+    let mut placed = vec![first];
+    for &rect in &bounds[1..] {
+        let previous = *placed.last().unwrap();
+        let delta = along(next_to(rect, previous, direction, buff), direction)
+            + along(edge(first, align) - edge(rect, align), perpendicular);
+
+        placed.push(rect.translate(delta));
+    }
+
+    recenter(bounds, placed)
+}
+
+/// How far each of `bounds` has to move to be laid out in a grid, row by row,
+/// with `columns` columns. Each item is centered in its cell, every column is
+/// as wide as its widest item, and every row as tall as its tallest one.
+pub fn arrange_in_grid(bounds: &[Rect], columns: usize, buff: Vec2) -> Vec<Vec2> {
+    let columns = columns.max(1);
+    let rows = bounds.len().div_ceil(columns);
+
+    let mut widths = vec![0.0_f32; columns];
+    let mut heights = vec![0.0_f32; rows];
+    for (i, rect) in bounds.iter().enumerate() {
+        widths[i % columns] = widths[i % columns].max(rect.width());
+        heights[i / columns] = heights[i / columns].max(rect.height());
+    }
+
+    let offsets = |sizes: &[f32], buff: f32| {
+        sizes
+            .iter()
+            .scan(0.0, |start, size| {
+                let center = *start + size / 2.0;
+                *start += size + buff;
+                Some(center)
+            })
+            .collect::<Vec<_>>()
+    };
+    let (x_offsets, y_offsets) = (offsets(&widths, buff.x), offsets(&heights, buff.y));
+
+    let placed = bounds
+        .iter()
+        .enumerate()
+        .map(|(i, rect)| {
+            let center = pos2(x_offsets[i % columns], y_offsets[i / columns]);
+            rect.translate(center - rect.center())
+        })
+        .collect();
+
+    recenter(bounds, placed)
+}
+
+/// The moves from `bounds` to `placed`, with `placed` shifted as a whole to
+/// be centered where `bounds` were.
+fn recenter(bounds: &[Rect], placed: Vec<Rect>) -> Vec<Vec2> {
+    let union = |rects: &[Rect]| {
+        rects
+            .iter()
+            .fold(Rect::NOTHING, |acc, rect| acc.union(*rect))
+    };
+    let shift = union(bounds).center() - union(&placed).center();
+
+    bounds
+        .iter()
+        .zip(placed)
+        .map(|(rect, placed)| placed.min - rect.min + shift)
+        .collect()
+}
+
+/// The position an object needs to have for its world bounds to move by
+/// `delta`, in the space of its parent.
+fn moved_position(world: &World, object_id: ObjectId, delta: Vec2) -> Pos2 {
+    let parent = world.objects.flattened_transform(object_id);
+    let delta = Vec2::angled(delta.angle() - parent.rotation) * delta.length() / parent.scale;
+
+    world.objects[&object_id].transform.position + delta
+}
+
+fn set_position(world: &mut World, object_id: ObjectId, position: Pos2) {
+    world
+        .objects
+        .get_mut(&object_id)
+        .unwrap()
+        .transform
+        .position = position;
+}
+
+/// The position that places an object next to another one.
+#[derive(Clone)]
+pub struct NextTo {
+    object_id: ObjectId,
+    target: ObjectId,
+    direction: Vec2,
+    buff: f32,
+}
+
+impl NextTo {
+    pub fn new<C: Component, T: Component>(
+        handle: &Handle<C>,
+        target: &Handle<T>,
+        direction: Vec2,
+        buff: f32,
+    ) -> Self {
+        Self {
+            object_id: handle.object_id,
+            target: target.object_id,
+            direction,
+            buff,
+        }
+    }
+}
+
+impl Dynamic<Pos2> for NextTo {
+    fn get(&self, world: &World) -> Pos2 {
+        let delta = next_to(
+            world.objects.bounding_box(self.object_id),
+            world.objects.bounding_box(self.target),
+            self.direction,
+            self.buff,
+        );
+
+        moved_position(world, self.object_id, delta)
+    }
+}
+
+/// The position that lines an object's edge up with the same edge of another
+/// object.
+#[derive(Clone)]
+pub struct AlignTo {
+    object_id: ObjectId,
+    target: ObjectId,
+    direction: Vec2,
+}
+
+impl AlignTo {
+    pub fn new<C: Component, T: Component>(
+        handle: &Handle<C>,
+        target: &Handle<T>,
+        direction: Vec2,
+    ) -> Self {
+        Self {
+            object_id: handle.object_id,
+            target: target.object_id,
+            direction,
+        }
+    }
+}
+
+impl Dynamic<Pos2> for AlignTo {
+    fn get(&self, world: &World) -> Pos2 {
+        let delta = align_to(
+            world.objects.bounding_box(self.object_id),
+            world.objects.bounding_box(self.target),
+            self.direction,
+        );
+
+        moved_position(world, self.object_id, delta)
+    }
+}
+
+/// The position that moves an object to an edge or corner of the frame.
+#[derive(Clone)]
+pub struct ToEdge {
+    object_id: ObjectId,
+    direction: Vec2,
+    buff: f32,
+}
+
+impl ToEdge {
+    pub fn new<C: Component>(handle: &Handle<C>, direction: Vec2, buff: f32) -> Self {
+        Self {
+            object_id: handle.object_id,
+            direction,
+            buff,
+        }
+    }
+}
+
+impl Dynamic<Pos2> for ToEdge {
+    fn get(&self, world: &World) -> Pos2 {
+        let delta = to_edge(
+            world.objects.bounding_box(self.object_id),
+            world.frame(),
+            self.direction,
+            self.buff,
+        );
+
+        moved_position(world, self.object_id, delta)
+    }
+}
+
+/// Moves an object to a position, once, at this point of the scene. Motions
+/// after it still move the object, and the placement doesn't follow what it
+/// depends on unless it is made [`continuous`](Place::continuous).
+pub struct Place {
+    pub object_id: ObjectId,
+    pub position: OwnedDynamic<Pos2>,
+}
+
+impl Trigger for Place {
+    fn trigger(&self, world: &mut World) {
+        let position = self.position.get(world);
+        set_position(world, self.object_id, position);
+    }
+}
+
+impl Place {
+    /// Keeps the object in place every frame from now on, after every motion,
+    /// so it follows the objects it is placed relative to.
+    pub fn continuous(self) -> AddUpdater {
+        AddUpdater::new(move |world| self.trigger(world))
+    }
+}
+
+impl<C: Component> Handle<C> {
+    pub fn place(&self, position: impl Dynamic<Pos2>) -> Place {
+        Place {
+            object_id: self.object_id,
+            position: OwnedDynamic::new(position),
+        }
+    }
+
+    pub fn next_to<T: Component>(&self, target: &Handle<T>, direction: Vec2, buff: f32) -> Place {
+        self.place(NextTo::new(self, target, direction, buff))
+    }
+
+    pub fn align_to<T: Component>(&self, target: &Handle<T>, direction: Vec2) -> Place {
+        self.place(AlignTo::new(self, target, direction))
+    }
+
+    pub fn to_edge(&self, direction: Vec2, buff: f32) -> Place {
+        self.place(ToEdge::new(self, direction, buff))
+    }
+
+    /// Moves the object to a corner of the frame, e.g., [`UP_LEFT`].
+    pub fn to_corner(&self, corner: Vec2, buff: f32) -> Place {
+        self.to_edge(corner, buff)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Arrangement {
+    Line {
+        direction: Vec2,
+        buff: f32,
+        align: Vec2,
+    },
+    Grid {
+        columns: usize,
+        buff: Vec2,
+    },
+}
+
+/// Lays several objects out relative to each other, once, at this point of
+/// the scene, or every frame if made [`continuous`](Arrange::continuous).
+#[derive(Clone)]
+pub struct Arrange {
+    objects: Rc<[ObjectId]>,
+    arrangement: Arrangement,
+}
+
+impl Arrange {
+    pub fn new(objects: Vec<ObjectId>, direction: Vec2, buff: f32, align: Vec2) -> Self {
+        Self {
+            objects: objects.into(),
+            arrangement: Arrangement::Line {
+                direction,
+                buff,
+                align,
+            },
+        }
+    }
+
+    /// A grid with `columns` columns, `buff.x` between columns and `buff.y`
+    /// between rows.
+    pub fn grid(objects: Vec<ObjectId>, columns: usize, buff: Vec2) -> Self {
+        Self {
+            objects: objects.into(),
+            arrangement: Arrangement::Grid { columns, buff },
+        }
+    }
+
+    /// Where every object has to be for the arrangement.
+    pub fn positions(&self, world: &World) -> Vec<Pos2> {
+        let bounds: Vec<_> = self
+            .objects
+            .iter()
+            .map(|id| world.objects.bounding_box(*id))
+            .collect();
+
+        let deltas = match self.arrangement {
+            Arrangement::Line {
+                direction,
+                buff,
+                align,
+            } => arrange(&bounds, direction, buff, align),
+            Arrangement::Grid { columns, buff } => arrange_in_grid(&bounds, columns, buff),
+        };
+
+        self.objects
+            .iter()
+            .zip(deltas)
+            .map(|(id, delta)| moved_position(world, *id, delta))
+            .collect()
+    }
+
+    /// The position of the `index`th object in the arrangement.
+    pub fn position(&self, index: usize) -> ArrangedPosition {
+        ArrangedPosition {
+            arrange: self.clone(),
+            index,
+        }
+    }
+
+    pub fn continuous(self) -> AddUpdater {
+        AddUpdater::new(move |world| self.trigger(world))
+    }
+}
+
+impl Trigger for Arrange {
+    fn trigger(&self, world: &mut World) {
+        for (id, position) in self.objects.iter().zip(self.positions(world)) {
+            set_position(world, *id, position);
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ArrangedPosition {
+    arrange: Arrange,
+    index: usize,
+}
+
+impl Dynamic<Pos2> for ArrangedPosition {
+    fn get(&self, world: &World) -> Pos2 {
+        self.arrange.positions(world)[self.index]
+    }
+}
+
+impl<C: Component> GroupHandle<C> {
+    fn object_ids(&self) -> Vec<ObjectId> {
+        self.children.iter().map(|child| child.object_id).collect()
+    }
+
+    /// Lines the children up in `direction`, see [`arrange`].
+    pub fn arrange(&self, direction: Vec2, buff: f32, align: Vec2) -> Arrange {
+        Arrange::new(self.object_ids(), direction, buff, align)
+    }
+
+    pub fn arrange_in_grid(&self, columns: usize, buff: Vec2) -> Arrange {
+        Arrange::grid(self.object_ids(), columns, buff)
+    }
+}
@@ -0,0 +1,35 @@
+pub mod animation;
+pub mod axes;
+pub mod builder;
+pub mod camera;
+pub mod component;
+pub mod dynamics;
+pub mod easing;
+pub mod export;
+pub mod field;
+pub mod golden;
+pub mod group;
+pub mod interpolation;
+pub mod layout;
+pub mod mesh;
+pub mod motion;
+pub mod number;
+pub mod object;
+pub mod object_tree;
+pub mod plot;
+pub mod probe;
+pub mod rasterizer;
+pub mod registry;
+#[cfg(feature = "player")]
+pub mod renderer;
+pub mod scene;
+pub mod serialization;
+pub mod shapes;
+pub mod spacing;
+pub mod svg;
+pub mod timing;
+pub mod tracking;
+pub mod trigger;
+pub mod typst;
+pub mod utils;
+pub mod world;
@@ -1,217 +1,65 @@
-use animation::{Animation, MotionAnimation};
-use builder::Builder;
-use component::{Component, ComponentExt, Handle};
-use dynamics::{Dynamic, OwnedDynamic};
-use easing::Easing::{self, EaseInOut};
-use egui::{pos2, Color32, Pos2, Stroke};
-use group::{Group, GroupHandle};
+use egui::{pos2, vec2, Color32, Pos2};
+use enimate::{
+    animation::{Animation, MotionAnimation},
+    axes::{AxisRange, NumberPlane},
+    builder::Builder,
+    component::{Component, ComponentExt, Handle},
+    dynamics::{zip, DynamicExt, Noise, Oscillator, OwnedDynamic, SceneTime},
+    easing::Easing::{self, EaseInOut},
+    field::{StreamLines, VectorField},
+    group::Group,
+    layout::{DOWN, RIGHT, UP, UP_RIGHT},
+    motion::{EmbededScene, FadeIn, Motion},
+    number::DecimalNumber,
+    object::{FillMaterial, Material, Model, Object, StrokeMaterial, Transform},
+    plot::{FunctionGraph, ParametricCurve},
+    register_scene,
+    registry::{SceneEntry, SceneRegistry},
+    scene::{Scene, SceneBuilder},
+    shapes::{Arc, Arrow, ArrowHead, Circle, Line, RegularPolygon, RoundedRect, Star, Vector},
+    spacing::Alignment,
+    timing::{Concurrently, Sequence, Wait},
+    tracking::{BoundsOf, PointOnPath, PositionOf},
+    typst::Typst,
+    world::MOUSE_POSITION,
+};
 use lyon::{math::point, path::Path};
-use motion::{EmbededScene, FadeIn, Motion, Move};
-use object::{FillMaterial, Material, Model, Object, ObjectId, StrokeMaterial, Transform};
-use renderer::Renderer;
-use scene::{Scene, SceneBuilder};
-use shapes::{Circle, Line};
-use spacing::Alignment;
-use std::collections::HashMap;
-use timing::{Concurrently, Sequence, Wait};
-use typst::Typst;
-
-use crate::renderer::UNIT_GRID_HEIGHT;
-
-mod animation;
-mod builder;
-mod component;
-mod dynamics;
-mod easing;
-mod group;
-mod mesh;
-mod motion;
-mod object;
-mod object_tree;
-mod renderer;
-mod scene;
-mod shapes;
-mod spacing;
-mod timing;
-mod trigger;
-mod typst;
-mod utils;
-mod world;
-
-struct App {
-    current_scene: usize,
-    scenes: Vec<(&'static str, Scene)>,
-    renderer: Renderer,
-    play: bool,
-    current_time: f32,
-}
-
-impl App {
-    fn scene(&self) -> &Scene {
-        &self.scenes[self.current_scene].1
-    }
-
-    fn scene_mut(&mut self) -> &mut Scene {
-        &mut self.scenes[self.current_scene].1
-    }
-}
-
-impl eframe::App for App {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        let dt = ctx.input(|i| i.stable_dt) as f32;
-
-        if self.play && self.current_time < self.scene().length() {
-            self.current_time += dt;
-        }
-
-        if self.current_time >= self.scene().length() {
-            self.play = false;
-            self.current_time = self.scene().length();
-        }
-
-        ctx.request_repaint();
-
-        if ctx.input(|i| i.key_pressed(egui::Key::Space)) {
-            if self.current_time >= self.scene().length() {
-                self.current_time = 0.0;
-                self.play = true;
-            } else {
-                self.play = !self.play;
-            }
-        }
-
-        egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
-            egui::ComboBox::from_label("Scene")
-                .selected_text(format!("{}", self.scenes[self.current_scene].0))
-                .show_ui(ui, |ui| {
-                    for (i, (name, _)) in self.scenes.iter().enumerate() {
-                        if ui
-                            .selectable_value(&mut self.current_scene, i, *name)
-                            .clicked()
-                        {
-                            self.current_time = 0.0;
-                            self.play = true;
-                        }
-                    }
-                });
-        });
-
-        // egui::SidePanel::left("scene_panel").show(ctx, |ui| {
-        //     egui::ScrollArea::vertical().show(ui, |ui| {
-        //         ui.heading("Scene Tree");
-        //         ui.separator();
-        //         let root = self.scene().root;
-        //         fixme(ui, self.scene_mut(), root);
-        //         // self.scene.root_mut().ui(ui, &mut self.scene);
-        //     });
-        // });
-
-        egui::CentralPanel::default().show(ctx, |ui| {
-            ui.label(format!("Time: {}", self.current_time));
-            if self.play {
-                if ui.button("Pause").clicked() {
-                    self.play = false;
-                }
-            } else {
-                if ui.button("Play").clicked() {
-                    self.play = true;
-                }
+use std::process::ExitCode;
+
+mod cli;
+#[cfg(feature = "player")]
+mod player;
+
+fn main() -> ExitCode {
+    // Logs go to stderr, so renders can be written to stdout.
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .init();
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    #[cfg(feature = "player")]
+    if args.is_empty() {
+        return match player::run(SceneRegistry::collect()) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(error) => {
+                eprintln!("error: {error}");
+                ExitCode::FAILURE
             }
-
-            ui.with_layout(egui::Layout::bottom_up(egui::Align::Center), |ui| {
-                let length = self.scene().length();
-
-                ui.add(
-                    egui::Slider::new(&mut self.current_time, 0.0..=length).clamp_to_range(true),
-                );
-
-                egui::Frame::canvas(ui.style()).show(ui, |ui| {
-                    let rect = ui.available_rect_before_wrap();
-                    let _response = ui.allocate_rect(rect, egui::Sense::drag());
-
-                    let mut input = HashMap::new();
-                    let pos = ctx.input(|i| i.pointer.hover_pos()).unwrap_or(Pos2::ZERO);
-
-                    let pos = pos - rect.center().to_vec2();
-
-                    input.insert(0, pos.x);
-                    input.insert(1, pos.y);
-
-                    let current_time = self.current_time;
-                    let size = rect.size();
-                    let objects =
-                        self.scene_mut()
-                            .render_with_input(current_time, (size.x, size.y), input);
-
-                    let boxes = objects.bounding_boxes();
-
-                    self.renderer.paint_at(ui, rect, objects);
-
-                    if false {
-                        let bb_canvas = ui.painter_at(rect);
-                        for (_id, bb) in boxes {
-                            bb_canvas.rect_stroke(
-                                (bb * (size.y / UNIT_GRID_HEIGHT))
-                                    .translate(rect.center().to_vec2()),
-                                0.0,
-                                Stroke::new(1.0, Color32::RED),
-                            );
-                        }
-                    }
-                });
-            });
-        });
+        };
     }
-}
 
-impl App {
-    fn new<'a>(cc: &'a eframe::CreationContext<'a>, scenes: Vec<(&'static str, Scene)>) -> Self {
-        let renderer = Renderer::new(cc).unwrap();
+    // Without the player, there is only the command line.
+    let args = if args.is_empty() {
+        vec!["help".into()]
+    } else {
+        args
+    };
 
-        Self {
-            current_scene: scenes.len() - 1,
-            scenes,
-            renderer,
-            play: true,
-            current_time: 0.0,
-        }
-    }
+    cli::run(&args, SceneRegistry::collect())
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    tracing_subscriber::fmt::init();
-
-    let mut native_options = eframe::NativeOptions::default();
-    native_options.renderer = eframe::Renderer::Wgpu;
-
-    eframe::run_native(
-        "My egui App",
-        native_options,
-        Box::new(|cc| {
-            Box::new(App::new(
-                cc,
-                vec![
-                    // ("Mouse input", mouse_input()),
-                    ("Stroke", stroke()),
-                    // ("Building", building()),
-                    ("Animations", animations()),
-                    ("Movement", movement()),
-                    // ("Variables", variables()),
-                    ("Scenes", embedded_scenes()),
-                    ("Dynamic Alignment", dynamic_alignment()),
-                    ("Render Grid", render_grid()),
-                    ("Grid", grid()),
-                    ("Animating Component Children", animate_component_children()),
-                    ("Typst", typst_example()),
-                    ("Dynamic path", dynamic_line()),
-                    ("Component animations", component_animations()),
-                ],
-            ))
-        }),
-    )?;
-
-    Ok(())
-}
+register_scene!(SceneEntry::new("Stroke", stroke).with_tags(&["paths"]));
 
 fn stroke() -> Scene {
     let mut builder = Path::builder();
@@ -240,6 +88,8 @@ fn stroke() -> Scene {
     b.finish()
 }
 
+register_scene!(SceneEntry::new("Animations", animations).with_tags(&["animation"]));
+
 fn animations() -> Scene {
     let mut b = SceneBuilder::new();
 
@@ -260,7 +110,7 @@ fn animations() -> Scene {
         seq.add(MotionAnimation {
             duration: 0.3,
             motion: FadeIn {
-                object_id: **circle,
+                object_id: circle.object_id,
             },
             easing: Easing::Linear,
         });
@@ -273,6 +123,8 @@ fn animations() -> Scene {
     b.finish()
 }
 
+register_scene!(SceneEntry::new("Movement", movement).with_tags(&["animation"]));
+
 fn movement() -> Scene {
     let mut b = SceneBuilder::new();
 
@@ -304,6 +156,8 @@ fn movement() -> Scene {
     b.finish()
 }
 
+register_scene!(SceneEntry::new("Scenes", embedded_scenes).with_tags(&["animation", "composition"]));
+
 pub fn embedded_scenes() -> Scene {
     let mut b = SceneBuilder::new();
 
@@ -336,6 +190,10 @@ pub fn embedded_scenes() -> Scene {
     b.finish()
 }
 
+register_scene!(
+    SceneEntry::new("Dynamic Alignment", dynamic_alignment).with_tags(&["dynamics", "layout"])
+);
+
 fn dynamic_alignment() -> Scene {
     let mut b = SceneBuilder::new();
 
@@ -356,7 +214,10 @@ fn dynamic_alignment() -> Scene {
 
     b.play(
         left_circle
-            .mv(pos2(1.0, 1.0), Alignment::new(**right_circle).left())
+            .mv(
+                pos2(1.0, 1.0),
+                Alignment::new(right_circle.object_id).left(),
+            )
             .with_duration(1.0)
             .with_easing(EaseInOut),
     );
@@ -364,6 +225,8 @@ fn dynamic_alignment() -> Scene {
     b.finish()
 }
 
+register_scene!(SceneEntry::new("Grid", grid).with_tags(&["components"]));
+
 fn grid() -> Scene {
     let mut b = SceneBuilder::new();
 
@@ -378,6 +241,11 @@ fn grid() -> Scene {
     b.finish()
 }
 
+register_scene!(
+    SceneEntry::new("Animating Component Children", animate_component_children)
+        .with_tags(&["components", "animation"])
+);
+
 fn animate_component_children() -> Scene {
     let mut b = SceneBuilder::new();
 
@@ -447,7 +315,7 @@ impl Component for Grid {
             let x = (i as f32 / self.cols as f32) * self.width - self.width / 2.0;
             let y = -self.height / 2.0;
 
-            let line = vertical_lines.add(Line {
+            vertical_lines.add(Line {
                 start: pos2(x, y),
                 end: pos2(x, y + self.height),
                 material: self.material.clone(),
@@ -463,20 +331,18 @@ impl Component for Grid {
 }
 
 impl GridHandle {
-    fn draw_out(&self, duration: f32) -> Concurrently {
+    fn draw_out(&mut self, duration: f32) -> Concurrently {
         let mut c = Concurrently::default();
 
-        for horizontal_line in &self.horizontal_lines.children {
-            c.add(
-                horizontal_line
-                    .animate(Some(horizontal_line.start), Some(horizontal_line.start))
-                    .with_duration(duration),
-            );
-        }
-        for vertical_line in &self.vertical_lines.children {
+        for line in self
+            .horizontal_lines
+            .children
+            .iter_mut()
+            .chain(self.vertical_lines.children.iter_mut())
+        {
+            let start = line.start;
             c.add(
-                vertical_line
-                    .animate(Some(vertical_line.start), Some(vertical_line.start))
+                line.animate(Some(start), Some(start))
                     .with_duration(duration),
             );
         }
@@ -485,12 +351,14 @@ impl GridHandle {
     }
 }
 
+register_scene!(SceneEntry::new("Render Grid", render_grid).with_tags(&["components"]));
+
 fn render_grid() -> Scene {
     let mut b = SceneBuilder::new();
 
     for y in 0..=8 {
         for x in 0..16 {
-            let circle = b.add(
+            b.add(
                 Circle {
                     radius: 0.1,
                     material: FillMaterial::new(Color32::RED).into(),
@@ -503,6 +371,8 @@ fn render_grid() -> Scene {
     b.finish()
 }
 
+register_scene!(SceneEntry::new("Typst", typst_example).with_tags(&["text"]));
+
 fn typst_example() -> Scene {
     let mut b = SceneBuilder::new();
 
@@ -515,10 +385,12 @@ fn typst_example() -> Scene {
     b.finish()
 }
 
+register_scene!(SceneEntry::new("Dynamic path", dynamic_line).with_tags(&["paths", "dynamics"]));
+
 fn dynamic_line() -> Scene {
     let mut b = SceneBuilder::new();
 
-    let line = b.add(Line {
+    let mut line = b.add(Line {
         start: pos2(-1.0, 0.0),
         end: pos2(1.0, 0.0),
         material: StrokeMaterial::new(Color32::RED, 0.1).into(),
@@ -532,10 +404,15 @@ fn dynamic_line() -> Scene {
     b.finish()
 }
 
+register_scene!(
+    SceneEntry::new("Component animations", component_animations)
+        .with_tags(&["components", "animation"])
+);
+
 fn component_animations() -> Scene {
     let mut b = SceneBuilder::new();
 
-    let grid = b.add(Grid {
+    let mut grid = b.add(Grid {
         rows: 10,
         cols: 10,
         width: 8.0,
@@ -548,3 +425,596 @@ fn component_animations() -> Scene {
 
     b.finish()
 }
+
+register_scene!(
+    SceneEntry::new("Transform matching", transform_matching).with_tags(&["text", "animation"])
+);
+
+fn transform_matching() -> Scene {
+    let mut b = SceneBuilder::new();
+
+    let pythagoras = b.add(Typst {
+        text: r#"$a^2 + b^2 = c^2$"#.to_string(),
+        material: FillMaterial::new(Color32::WHITE).into(),
+    });
+
+    b.play(Wait.with_duration(0.5));
+
+    let rearranged = pythagoras.transform_matching(Typst {
+        text: r#"$c^2 = a^2 + b^2$"#.to_string(),
+        material: FillMaterial::new(Color32::WHITE).into(),
+    });
+    let target = rearranged.target();
+    b.play(rearranged.with_duration(1.5).with_easing(Easing::EaseInOut));
+
+    b.play(Wait.with_duration(0.5));
+
+    b.play(
+        target
+            .transform_matching(Typst {
+                text: r#"$c = sqrt(a^2 + b^2)$"#.to_string(),
+                material: FillMaterial::new(Color32::WHITE).into(),
+            })
+            .with_key("$c^2$", "$c$")
+            .with_duration(1.5)
+            .with_easing(Easing::EaseInOut),
+    );
+
+    b.finish()
+}
+
+register_scene!(SceneEntry::new("Shapes", shapes).with_tags(&["shapes"]));
+
+fn shapes() -> Scene {
+    let mut b = SceneBuilder::new();
+
+    let mut polygon = b.add(
+        RegularPolygon {
+            sides: 3,
+            radius: 1.0,
+            material: FillMaterial::new(Color32::RED).into(),
+        }
+        .with_position(pos2(-4.5, 0.0)),
+    );
+    let mut rect = b.add(
+        RoundedRect {
+            size: vec2(2.0, 2.0),
+            corner_radius: 0.0,
+            material: StrokeMaterial::new(Color32::BLUE, 0.1).into(),
+        }
+        .with_position(pos2(-1.5, 0.0)),
+    );
+    let mut arc = b.add(
+        Arc {
+            radius: 1.0,
+            start_angle: 0.0,
+            sweep_angle: 0.0,
+            material: StrokeMaterial::new(Color32::GREEN, 0.1).into(),
+        }
+        .with_position(pos2(1.5, 0.0)),
+    );
+    let mut star = b.add(
+        Star {
+            points: 5,
+            outer_radius: 1.0,
+            inner_radius: 0.4,
+            material: FillMaterial::new(Color32::YELLOW).into(),
+        }
+        .with_position(pos2(4.5, 0.0)),
+    );
+
+    let mut c = Concurrently::default();
+    c.add(polygon.animate(Some(8), None).with_duration(1.5));
+    c.add(rect.animate(None, Some(1.0)).with_duration(1.5));
+    c.add(
+        arc.animate(None, None, Some(std::f32::consts::TAU))
+            .with_duration(1.5),
+    );
+    c.add(star.animate(Some(8), None, Some(0.8)).with_duration(1.5));
+
+    b.play(c.with_easing(Easing::EaseInOut));
+
+    b.finish()
+}
+
+register_scene!(SceneEntry::new("Arrows", arrows).with_tags(&["shapes"]));
+
+fn arrows() -> Scene {
+    let mut b = SceneBuilder::new();
+
+    let left = b.add(
+        Circle {
+            radius: 0.5,
+            material: FillMaterial::new(Color32::RED).into(),
+        }
+        .with_position(pos2(-3.0, 0.0)),
+    );
+    let right = b.add(
+        Circle {
+            radius: 0.5,
+            material: FillMaterial::new(Color32::BLUE).into(),
+        }
+        .with_position(pos2(3.0, 0.0)),
+    );
+
+    b.add(
+        Arrow::new(
+            Alignment::new(left.object_id).right(),
+            Alignment::new(right.object_id).left(),
+            StrokeMaterial::new(Color32::WHITE, 0.05),
+        )
+        .with_head(ArrowHead::Stealth)
+        .with_head_size(0.3),
+    );
+    b.add(
+        Arrow::new(
+            Alignment::new(left.object_id).top(),
+            Alignment::new(right.object_id).top(),
+            StrokeMaterial::new(Color32::YELLOW, 0.05),
+        )
+        .with_head(ArrowHead::Open)
+        .with_head_size(0.3)
+        .with_bend(-0.3)
+        .double_headed(),
+    );
+
+    let mut c = Concurrently::default();
+    c.add(
+        left.mv(pos2(-3.0, 0.0), pos2(-1.0, 2.0))
+            .with_duration(1.5)
+            .with_easing(Easing::EaseInOut),
+    );
+    c.add(
+        right
+            .mv(pos2(3.0, 0.0), pos2(2.0, -2.0))
+            .with_duration(1.5)
+            .with_easing(Easing::EaseInOut),
+    );
+    b.play(c);
+
+    b.finish()
+}
+
+register_scene!(SceneEntry::new("Number plane", number_plane).with_tags(&["math"]));
+
+fn number_plane() -> Scene {
+    let mut b = SceneBuilder::new();
+
+    let plane = b.add(
+        NumberPlane::new(
+            AxisRange::new(-5.0, 5.0, 1.0),
+            AxisRange::new(-3.0, 3.0, 1.0),
+        )
+        .with_axes(|axes| {
+            axes.with_tick_labels(true)
+                .with_x_label("$x$")
+                .with_y_label("$y$")
+        }),
+    );
+
+    let dot = b.add(Circle {
+        radius: 0.1,
+        material: FillMaterial::new(Color32::YELLOW).into(),
+    });
+    b.add(
+        Vector::new(
+            PositionOf::new(&dot),
+            StrokeMaterial::new(Color32::YELLOW, 0.05),
+        )
+        .with_head_size(0.25),
+    );
+
+    b.play(
+        dot.mv(plane.coords(-4.0, -2.0), plane.coords(3.0, 2.0))
+            .with_duration(2.0)
+            .with_easing(Easing::EaseInOut),
+    );
+
+    b.finish()
+}
+
+register_scene!(SceneEntry::new("Plots", plots).with_tags(&["math"]));
+
+fn plots() -> Scene {
+    let mut b = SceneBuilder::new();
+
+    let plane = b.add(NumberPlane::new(
+        AxisRange::new(-5.0, 5.0, 1.0),
+        AxisRange::new(-3.0, 3.0, 1.0),
+    ));
+
+    b.add(
+        FunctionGraph::new(
+            |x| x.sin() * 2.0,
+            (-5.0, 5.0),
+            StrokeMaterial::new(Color32::YELLOW, 0.05),
+        )
+        .with_axes(&plane.axes),
+    );
+    b.add(
+        FunctionGraph::new(
+            |x| 1.0 / x,
+            (-5.0, 5.0),
+            StrokeMaterial::new(Color32::LIGHT_RED, 0.05),
+        )
+        .with_axes(&plane.axes),
+    );
+    b.add(
+        ParametricCurve::new(
+            |t| pos2((3.0 * t).cos() * 2.0, (2.0 * t).sin() * 2.0),
+            (0.0, std::f32::consts::TAU),
+            StrokeMaterial::new(Color32::LIGHT_BLUE, 0.05),
+        )
+        .with_axes(&plane.axes),
+    );
+
+    b.finish()
+}
+
+register_scene!(SceneEntry::new("Vector field", vector_field).with_tags(&["math"]));
+
+fn vector_field() -> Scene {
+    let mut b = SceneBuilder::new();
+
+    let field = |p: Pos2| vec2(-p.y, p.x) * 0.5 + p.to_vec2() * -0.1;
+
+    let arrows = b.add(VectorField::new(field, (-6.0, 6.0), (-3.5, 3.5)));
+    b.play(arrows.grow(1.0).with_easing(Easing::EaseInOut));
+
+    let lines = b.add(
+        StreamLines::new(field, (-6.0, 6.0), (-3.5, 3.5))
+            .with_seed_spacing(1.5)
+            .with_stroke_width(0.04),
+    );
+    b.play(lines.create(2.0).then(lines.flow(6.0, 1.0)));
+
+    b.finish()
+}
+
+register_scene!(SceneEntry::new("Counter", counter).with_tags(&["text", "dynamics"]));
+
+fn counter() -> Scene {
+    let mut b = SceneBuilder::new();
+
+    let distance = b.add(
+        DecimalNumber::new(0.0)
+            .with_decimals(1)
+            .with_thousands_separator(',')
+            .with_unit("km")
+            .with_transform(Transform::default().with_scale(3.0)),
+    );
+
+    b.play(
+        distance
+            .count(0.0, 12_345.6)
+            .with_duration(3.0)
+            .with_easing(Easing::EaseInOut),
+    );
+
+    b.finish()
+}
+
+register_scene!(SceneEntry::new("Variables", variables).with_tags(&["dynamics"]));
+
+fn variables() -> Scene {
+    let mut b = SceneBuilder::new();
+
+    let radius = b.variable(1.0);
+    let center = b.variable(pos2(-3.0, 0.0));
+
+    b.add(
+        Circle {
+            radius: 0.2,
+            material: FillMaterial::new(Color32::YELLOW).into(),
+        }
+        .with_position(MOUSE_POSITION),
+    );
+    b.add(
+        Circle {
+            radius: 0.5,
+            material: FillMaterial::new(Color32::BLUE).into(),
+        }
+        .with_position(center)
+        .with_scale(radius),
+    );
+    b.add(
+        DecimalNumber::new(radius)
+            .with_decimals(2)
+            .with_position(pos2(0.0, -3.0)),
+    );
+
+    b.play(center.tween_to(pos2(3.0, 0.0)).with_duration(2.0));
+    b.play(
+        radius
+            .tween_to(3.0)
+            .with_duration(1.0)
+            .with_easing(Easing::EaseInOut),
+    );
+    b.play(radius.set(0.5));
+    b.play(Wait.with_duration(1.0));
+
+    b.finish()
+}
+
+register_scene!(
+    SceneEntry::new("Dynamic expressions", dynamic_expressions).with_tags(&["text", "dynamics"])
+);
+
+fn dynamic_expressions() -> Scene {
+    let mut b = SceneBuilder::new();
+
+    let angle = b.variable(0.0);
+    let center = pos2(-2.0, 0.0);
+
+    b.add(
+        Circle {
+            radius: 0.1,
+            material: FillMaterial::new(Color32::WHITE).into(),
+        }
+        .with_position(center),
+    );
+
+    let orbit = angle.map(move |angle: f32| center + vec2(angle.cos(), -angle.sin()) * 2.0);
+    b.add(
+        Circle {
+            radius: 0.2,
+            material: FillMaterial::new(Color32::YELLOW).into(),
+        }
+        .with_position(orbit.clone()),
+    );
+    b.add(Arrow::new(
+        center,
+        orbit,
+        StrokeMaterial::new(Color32::WHITE, 0.04),
+    ));
+
+    b.add(
+        DecimalNumber::new(angle.owned() * 180.0 / std::f32::consts::PI)
+            .with_decimals(0)
+            .with_unit("°")
+            .with_position(pos2(3.0, 0.0)),
+    );
+
+    b.play(
+        angle
+            .tween_to(std::f32::consts::TAU)
+            .with_duration(4.0)
+            .with_easing(Easing::EaseInOut),
+    );
+
+    b.finish()
+}
+
+fn spinning_square() -> Scene {
+    let mut b = SceneBuilder::new();
+
+    b.add(
+        RegularPolygon {
+            sides: 4,
+            radius: 1.0,
+            material: FillMaterial::new(Color32::LIGHT_BLUE).into(),
+        }
+        .with_rotation(SceneTime::Local.owned() * 2.0),
+    );
+    b.play(Wait.with_duration(3.0));
+
+    b.finish()
+}
+
+register_scene!(SceneEntry::new("Continuous motion", continuous_motion).with_tags(&["dynamics"]));
+
+fn continuous_motion() -> Scene {
+    let mut b = SceneBuilder::new();
+
+    b.add(
+        Circle {
+            radius: 0.3,
+            material: FillMaterial::new(Color32::YELLOW).into(),
+        }
+        .with_position(Oscillator::new(1.5, 0.5).map(|y| pos2(-3.0, y))),
+    );
+    b.add(
+        Circle {
+            radius: 0.3,
+            material: FillMaterial::new(Color32::RED).into(),
+        }
+        .with_position(
+            zip(Noise::new(1, 1.0, 1.0), Noise::new(2, 1.0, 1.0)).map(|(x, y)| pos2(x, y)),
+        ),
+    );
+
+    b.play(Wait.with_duration(2.0));
+
+    // The square starts spinning when the embedded scene starts, since it
+    // follows the embedded scene's own time.
+    b.play(EmbededScene {
+        scene: spinning_square(),
+        transform: OwnedDynamic::new(Transform::default().with_position(pos2(3.0, 0.0))),
+        speed: 1.0,
+        object_id: rand::random::<usize>(),
+        rooted: true,
+    });
+
+    b.finish()
+}
+
+register_scene!(SceneEntry::new("Tracking", tracking).with_tags(&["dynamics"]));
+
+fn tracking() -> Scene {
+    let mut b = SceneBuilder::new();
+
+    let orbit = b.add(
+        Circle {
+            radius: 2.0,
+            material: StrokeMaterial::new(Color32::GRAY, 0.03).into(),
+        }
+        .with_position(pos2(-1.0, 0.0)),
+    );
+
+    let progress = SceneTime::Local.map(|t| (t / 4.0).fract());
+    let dot = b.add(
+        Circle {
+            radius: 0.15,
+            material: FillMaterial::new(Color32::YELLOW).into(),
+        }
+        .with_position(PointOnPath::new(&orbit, progress)),
+    );
+
+    b.add(
+        Typst {
+            text: "$p$".into(),
+            material: FillMaterial::new(Color32::WHITE).into(),
+        }
+        .with_position(PositionOf::new(&dot).owned() + vec2(0.4, -0.4)),
+    );
+
+    let square = b.add(
+        RoundedRect {
+            size: vec2(1.0, 1.0),
+            corner_radius: 0.1,
+            material: FillMaterial::new(Color32::LIGHT_BLUE).into(),
+        }
+        .with_position(pos2(4.0, 2.0)),
+    );
+
+    b.add(Arrow::new(
+        BoundsOf::new(&square).map(|bounds| bounds.center_top()),
+        PositionOf::new(&dot),
+        StrokeMaterial::new(Color32::WHITE, 0.04),
+    ));
+
+    b.play(
+        square
+            .mv(pos2(4.0, 2.0), pos2(4.0, -2.0))
+            .with_duration(4.0)
+            .with_easing(Easing::EaseInOut),
+    );
+
+    b.finish()
+}
+
+register_scene!(SceneEntry::new("Layout", layout).with_tags(&["layout"]));
+
+fn layout() -> Scene {
+    let mut b = SceneBuilder::new();
+
+    let title = b.add(Typst {
+        text: "= Layout".into(),
+        material: FillMaterial::new(Color32::WHITE).into(),
+    });
+    b.play(title.to_edge(UP, 0.3));
+
+    let mut row = Group::new();
+    for (i, size) in [0.3, 0.6, 0.4, 0.8].into_iter().enumerate() {
+        row.add(
+            RoundedRect {
+                size: vec2(size, size * 1.5),
+                corner_radius: 0.05,
+                material: FillMaterial::new(Color32::from_rgb(60 + 50 * i as u8, 120, 220)).into(),
+            }
+            .with_position(Pos2::ZERO),
+        );
+    }
+    let row = b.add(row);
+    b.play(row.arrange(RIGHT, 0.2, DOWN));
+
+    let mut grid = Group::new();
+    for i in 1..=9 {
+        grid.add(Typst {
+            text: format!("${}$", i * i * i),
+            material: FillMaterial::new(Color32::WHITE).into(),
+        });
+    }
+    let grid = b.add(grid.with_transform(Transform::default().with_position(pos2(0.0, 2.0))));
+    b.play(grid.arrange_in_grid(3, vec2(0.5, 0.2)));
+
+    let dot = b.add(
+        Circle {
+            radius: 0.2,
+            material: FillMaterial::new(Color32::YELLOW).into(),
+        }
+        .with_position(pos2(-4.0, -1.5)),
+    );
+    let label = b.add(Typst {
+        text: "$x$".into(),
+        material: FillMaterial::new(Color32::YELLOW).into(),
+    });
+    b.play(label.next_to(&dot, UP_RIGHT, 0.1).continuous());
+
+    b.play(
+        dot.mv(pos2(-4.0, -1.5), pos2(4.0, -1.5))
+            .with_duration(3.0)
+            .with_easing(Easing::EaseInOut),
+    );
+
+    b.finish()
+}
+
+register_scene!(SceneEntry::new("Camera", camera).with_tags(&["camera"]));
+
+fn camera() -> Scene {
+    let mut b = SceneBuilder::new();
+    let camera = b.camera();
+
+    let plane = b.add(NumberPlane::new(
+        AxisRange::new(-5.0, 5.0, 1.0),
+        AxisRange::new(-3.0, 3.0, 1.0),
+    ));
+
+    let star = b.add(Star {
+        points: 5,
+        inner_radius: 0.15,
+        outer_radius: 0.35,
+        material: FillMaterial::new(Color32::YELLOW).into(),
+    });
+    b.play(star.place(plane.coords(3.0, 2.0)));
+
+    let dot = b.add(Circle {
+        radius: 0.1,
+        material: FillMaterial::new(Color32::LIGHT_BLUE).into(),
+    });
+    b.play(dot.place(plane.coords(-4.0, -2.0)));
+
+    b.play(Wait.with_duration(0.5));
+
+    let mut c = Concurrently::default();
+    c.add(
+        camera
+            .focus_on(&star)
+            .with_duration(1.5)
+            .with_easing(EaseInOut),
+    );
+    c.add(
+        camera
+            .zoom_to(3.0)
+            .with_duration(1.5)
+            .with_easing(EaseInOut),
+    );
+    b.play(c);
+
+    // The camera follows the dot as it moves.
+    let mut c = Concurrently::default();
+    c.add(
+        dot.mv(plane.coords(-4.0, -2.0), plane.coords(2.0, 1.0))
+            .with_duration(2.0)
+            .with_easing(EaseInOut),
+    );
+    c.add(camera.move_to(PositionOf::new(&dot)).with_duration(2.0));
+    c.add(
+        camera
+            .rotate_to(0.3)
+            .with_duration(2.0)
+            .with_easing(EaseInOut),
+    );
+    b.play(c);
+
+    b.play(
+        camera
+            .zoom_to_fit(BoundsOf::new(&plane))
+            .with_duration(1.5)
+            .with_easing(EaseInOut),
+    );
+    b.play(camera.reset().with_duration(1.0).with_easing(EaseInOut));
+
+    b.finish()
+}
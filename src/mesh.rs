@@ -1,3 +1,4 @@
+#[cfg(feature = "player")]
 use eframe::egui_wgpu::wgpu;
 use egui::{pos2, Pos2, Rect};
 
@@ -8,11 +9,14 @@ pub struct Vertex {
 }
 
 impl Vertex {
-    const ATTRIBS: [wgpu::VertexAttribute; 1] = wgpu::vertex_attr_array![0 => Float32x2];
-
     pub fn new(pos: Pos2) -> Self {
         Self { pos }
     }
+}
+
+#[cfg(feature = "player")]
+impl Vertex {
+    const ATTRIBS: [wgpu::VertexAttribute; 1] = wgpu::vertex_attr_array![0 => Float32x2];
 
     pub fn desc() -> wgpu::VertexBufferLayout<'static> {
         use std::mem;
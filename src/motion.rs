@@ -1,11 +1,14 @@
 use crate::animation::{Animation, MotionAnimation};
-use crate::dynamics::{DynamicObject, DynamicTransform, OwnedDynamic};
+use crate::dynamics::OwnedDynamic;
 use crate::easing::Easing;
+use crate::interpolation::Interpolatable;
 use crate::object::{Object, ObjectId, ObjectKind, Transform};
+use crate::object_tree::ObjectTree;
 use crate::scene::Scene;
 use crate::trigger::Trigger;
-use crate::world::World;
-use egui::{Color32, Pos2};
+use crate::world::{Updater, Var, VariableType, World};
+use egui::Pos2;
+use std::rc::Rc;
 
 pub type Alpha = f32;
 
@@ -58,10 +61,22 @@ impl Motion for EmbededScene {
             render_size.1 / transform.scale,
         );
 
-        let objects = self.scene.render_at(
-            self.scene.length() * alpha * self.speed,
-            adjusted_render_size,
-        );
+        // Embedded scenes see the variables of the scene they are in. Their
+        // camera is ignored, since they are shown through the outer one.
+        let objects = self
+            .scene
+            .render_world(
+                World::new(
+                    ObjectTree::new(),
+                    adjusted_render_size,
+                    world.variables().clone(),
+                )
+                .with_time(
+                    self.scene.length() * alpha * self.speed,
+                    world.absolute_time(),
+                ),
+            )
+            .objects;
 
         let children = world.objects.merge(objects, self.object_id);
 
@@ -95,6 +110,22 @@ impl Trigger for AddObject {
     }
 }
 
+/// Registers an updater with the world, see [`World::add_updater`].
+#[derive(Clone)]
+pub struct AddUpdater(pub Updater);
+
+impl AddUpdater {
+    pub fn new(updater: impl Fn(&mut World) + 'static) -> Self {
+        Self(Rc::new(updater))
+    }
+}
+
+impl Trigger for AddUpdater {
+    fn trigger(&self, world: &mut World) {
+        world.add_updater(self.0.clone());
+    }
+}
+
 pub struct Move {
     pub from: OwnedDynamic<Pos2>,
     pub to: OwnedDynamic<Pos2>,
@@ -143,40 +174,144 @@ impl Motion for MoveTo {
     }
 }
 
+pub struct SetVariable<T: VariableType> {
+    pub variable: Var<T>,
+    pub value: T,
+}
+
+impl<T: VariableType> Trigger for SetVariable<T> {
+    fn trigger(&self, world: &mut World) {
+        world.set_variable(self.variable, self.value);
+    }
+}
+
+/// Tweens a variable from whatever value it has when the tween starts.
+pub struct TweenVariable<T: VariableType + Interpolatable> {
+    pub variable: Var<T>,
+    pub to: T,
+}
+
+impl<T: VariableType + Interpolatable> Motion for TweenVariable<T> {
+    fn animate(&self, world: &mut World, alpha: Alpha) {
+        // The world is rebuilt every frame, so this is the value set by the
+        // motions before this one.
+        let from = world.get_variable(self.variable);
+
+        world.set_variable(self.variable, from.interpolate(&self.to, alpha));
+    }
+}
+
+impl<T: VariableType> Var<T> {
+    pub fn set(&self, value: T) -> SetVariable<T> {
+        SetVariable {
+            variable: *self,
+            value,
+        }
+    }
+
+    pub fn tween_to(&self, to: T) -> TweenVariable<T>
+    where
+        T: Interpolatable,
+    {
+        TweenVariable {
+            variable: *self,
+            to,
+        }
+    }
+}
+
 pub struct FadeIn {
     pub object_id: usize,
 }
 
-fn fade_in(world: &mut World, alpha: Alpha, object_id: usize) {
-    let mut object = world.objects.remove(&object_id).unwrap();
+pub struct FadeOut {
+    pub object_id: usize,
+}
 
-    match &mut object.object_kind {
-        ObjectKind::Model(ref mut model) => {
-            if let Some(ref mut fill) = model.material.fill {
-                let c = fill.color;
-                fill.color =
-                    Color32::from_rgba_unmultiplied(c.r(), c.g(), c.b(), (alpha * 255.0) as u8);
-            }
+/// Fades every model of the object and its children to `opacity` of the
+/// alpha of its material.
+pub fn set_opacity(world: &mut World, opacity: f32, object_id: usize) {
+    let Some(object) = world.objects.get_mut(&object_id) else {
+        return;
+    };
 
-            if let Some(ref mut stroke) = model.material.stroke {
-                let c = stroke.color;
-                stroke.color =
-                    Color32::from_rgba_unmultiplied(c.r(), c.g(), c.b(), (alpha * 255.0) as u8);
-            }
-        }
+    match &mut object.object_kind {
+        ObjectKind::Model(model) => model.set_opacity(opacity),
         ObjectKind::Group(group) => {
-            for child_id in group.iter() {
-                fade_in(world, alpha, *child_id);
+            for child_id in group.clone() {
+                set_opacity(world, opacity, child_id);
             }
         }
     }
-
-    world.objects.add(object_id, object, false);
 }
 
 impl Motion for FadeIn {
     fn animate(&self, world: &mut World, alpha: f32) {
         // FIXME: We should be able to animate the alpha value of the color.
-        fade_in(world, alpha, self.object_id);
+        set_opacity(world, alpha, self.object_id);
+    }
+}
+
+impl Motion for FadeOut {
+    fn animate(&self, world: &mut World, alpha: f32) {
+        set_opacity(world, 1.0 - alpha, self.object_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        builder::Builder, group::Group, object::FillMaterial, probe::SceneProbe,
+        scene::SceneBuilder, shapes::Circle,
+    };
+    use egui::Color32;
+
+    fn circle(color: Color32) -> Circle {
+        Circle {
+            radius: 1.0,
+            material: FillMaterial::new(color).into(),
+        }
+    }
+
+    fn fill(probe: &SceneProbe, handle: &crate::component::Handle<Circle>, time: f32) -> Color32 {
+        let object = probe.object(handle, time).unwrap();
+        object.material().unwrap().fill.as_ref().unwrap().color
+    }
+
+    #[test]
+    fn fading_back_in_restores_the_color() {
+        let color = Color32::from_rgb(200, 100, 50);
+        let mut b = SceneBuilder::new();
+        let circle = b.add(circle(color));
+        b.play(circle.fade_out().with_duration(1.0));
+        b.play(circle.fade_in().with_duration(1.0));
+
+        let probe = SceneProbe::new(b.finish());
+        assert_eq!(fill(&probe, &circle, 1.0), Color32::TRANSPARENT);
+        probe.assert_opacity(&circle, 1.5, 0.5);
+        assert_eq!(fill(&probe, &circle, 2.0), color);
+    }
+
+    #[test]
+    fn fades_scale_the_alpha_of_each_model() {
+        let mut b = SceneBuilder::new();
+        let mut group = Group::new();
+        group.add(circle(Color32::WHITE));
+        group.add(circle(Color32::from_rgba_unmultiplied(255, 0, 0, 102)));
+        let group = b.add(group);
+        b.play(group.fade_out().with_duration(1.0));
+
+        let probe = SceneProbe::new(b.finish());
+        let alphas = |time| {
+            let object = probe.object(&group, time).unwrap();
+            object
+                .materials
+                .iter()
+                .map(|material| material.fill.as_ref().unwrap().color.a())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(alphas(0.0), [255, 102]);
+        assert_eq!(alphas(0.5), [128, 51]);
     }
 }
@@ -0,0 +1,290 @@
+use egui::Color32;
+use lyon::path::Path;
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    builder::Builder,
+    component::{Component, Handle},
+    dynamics::{Dynamic, OwnedDynamic},
+    interpolation::Interpolatable,
+    motion::{AddUpdater, Alpha, Motion},
+    object::{FillMaterial, Material, Object, ObjectId},
+    shapes::set_path,
+    typst::Typst,
+    world::{Var, World},
+};
+
+/// How a number is turned into text.
+#[derive(Clone, Debug)]
+pub struct NumberFormat {
+    pub decimals: usize,
+    pub thousands_separator: Option<char>,
+    /// Typst markup shown after the number, e.g., `$m/s^2$`.
+    pub unit: Option<String>,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            decimals: 2,
+            thousands_separator: None,
+            unit: None,
+        }
+    }
+}
+
+impl NumberFormat {
+    pub fn format(&self, value: f32) -> String {
+        let digits = format!("{:.*}", self.decimals, value.abs());
+        let (integer, fraction) = match digits.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (digits.as_str(), None),
+        };
+
+        let mut text = String::new();
+
+        // Rounding can turn a tiny negative value into zero, which shouldn't
+        // get a sign.
+        if value < 0.0 && digits.chars().any(|c| c != '0' && c != '.') {
+            text.push('−');
+        }
+
+        for (i, digit) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i) % 3 == 0 {
+                if let Some(separator) = self.thousands_separator {
+                    text.push(separator);
+                }
+            }
+            text.push(digit);
+        }
+
+        if let Some(fraction) = fraction {
+            text.push('.');
+            text.push_str(fraction);
+        }
+
+        if let Some(unit) = &self.unit {
+            text.push(' ');
+            text.push_str(unit);
+        }
+
+        text
+    }
+}
+
+/// Shows the formatted number on an object, compiling the glyphs again only
+/// when the text changes.
+#[derive(Clone)]
+struct NumberDisplay {
+    object_id: ObjectId,
+    format: Rc<NumberFormat>,
+    cache: Rc<RefCell<Option<(String, Path)>>>,
+}
+
+impl NumberDisplay {
+    fn show(&self, world: &mut World, value: f32) {
+        let text = self.format.format(value);
+        let mut cache = self.cache.borrow_mut();
+
+        if !matches!(&*cache, Some((cached, _)) if *cached == text) {
+            let path = Typst::merged_path(&Typst::compile(&text));
+            *cache = Some((text, path));
+        }
+
+        let (_, path) = cache.as_ref().unwrap();
+        set_path(world, self.object_id, path.clone());
+    }
+}
+
+/// A number that follows a dynamic value, such as a variable, centered on
+/// its position.
+pub struct DecimalNumber {
+    value: OwnedDynamic<f32>,
+    format: NumberFormat,
+    material: Material,
+}
+
+#[derive(Clone)]
+pub struct DecimalNumberHandle {
+    pub number: Handle<Object>,
+    /// Set by counts, and shown instead of the value the number follows once
+    /// a count has started.
+    count: Var<f32>,
+}
+
+impl DecimalNumber {
+    pub fn new(value: impl Dynamic<f32>) -> Self {
+        Self {
+            value: OwnedDynamic::new(value),
+            format: NumberFormat::default(),
+            material: FillMaterial::new(Color32::WHITE).into(),
+        }
+    }
+
+    pub fn with_decimals(mut self, decimals: usize) -> Self {
+        self.format.decimals = decimals;
+        self
+    }
+
+    pub fn with_thousands_separator(mut self, separator: char) -> Self {
+        self.format.thousands_separator = Some(separator);
+        self
+    }
+
+    /// Typst markup shown after the number.
+    pub fn with_unit(mut self, unit: impl Into<String>) -> Self {
+        self.format.unit = Some(unit.into());
+        self
+    }
+
+    pub fn with_format(mut self, format: NumberFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn with_material(mut self, material: impl Into<Material>) -> Self {
+        self.material = material.into();
+        self
+    }
+}
+
+impl Component for DecimalNumber {
+    type Handle = DecimalNumberHandle;
+
+    fn build<B: Builder>(self, builder: &mut B) -> Self::Handle {
+        let number = builder.add(Object::new_model(Path::new(), self.material));
+
+        let display = NumberDisplay {
+            object_id: *number,
+            format: Rc::new(self.format),
+            cache: Rc::new(RefCell::new(None)),
+        };
+
+        let value = self.value;
+        let count = Var::new(0.0);
+        builder.play(AddUpdater::new(move |world| {
+            // Motions run again from the start of the scene every frame, so
+            // the variable is only set once a count has started.
+            let value = if world.variables().contains_key(&count.id()) {
+                world.get_variable(count)
+            } else {
+                value.get(world)
+            };
+            display.show(world, value)
+        }));
+
+        DecimalNumberHandle { number, count }
+    }
+}
+
+impl DecimalNumberHandle {
+    /// Counts from one value to another. The count takes over from the value
+    /// the number follows, and keeps showing `to` once it is done.
+    pub fn count(&self, from: f32, to: f32) -> CountNumber {
+        CountNumber {
+            count: self.count,
+            from,
+            to,
+        }
+    }
+}
+
+pub struct CountNumber {
+    count: Var<f32>,
+    from: f32,
+    to: f32,
+}
+
+impl Motion for CountNumber {
+    fn animate(&self, world: &mut World, alpha: Alpha) {
+        world.set_variable(self.count, self.from.interpolate(&self.to, alpha));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        object::ObjectKind,
+        scene::{Scene, SceneBuilder},
+        serialization::to_path_data,
+        timing::Wait,
+    };
+
+    fn format(decimals: usize, separator: Option<char>) -> NumberFormat {
+        NumberFormat {
+            decimals,
+            thousands_separator: separator,
+            unit: None,
+        }
+    }
+
+    #[test]
+    fn format_groups_thousands() {
+        let format = format(0, Some(','));
+
+        assert_eq!(format.format(999.0), "999");
+        assert_eq!(format.format(1000.0), "1,000");
+        assert_eq!(format.format(1234567.0), "1,234,567");
+        assert_eq!(format.format(-12345.0), "−12,345");
+    }
+
+    #[test]
+    fn format_only_groups_the_integer_part() {
+        assert_eq!(format(2, Some(' ')).format(1234.5), "1 234.50");
+        assert_eq!(format(2, None).format(1234.5), "1234.50");
+    }
+
+    #[test]
+    fn format_rounds_to_the_decimals() {
+        assert_eq!(format(1, None).format(2.25), "2.2");
+        assert_eq!(format(1, None).format(2.26), "2.3");
+        assert_eq!(format(0, Some(',')).format(999.6), "1,000");
+    }
+
+    #[test]
+    fn format_never_shows_a_negative_zero() {
+        assert_eq!(format(2, None).format(-0.001), "0.00");
+        assert_eq!(format(0, None).format(-0.0), "0");
+        assert_eq!(format(2, None).format(-0.01), "−0.01");
+    }
+
+    #[test]
+    fn format_adds_the_unit() {
+        let format = NumberFormat {
+            unit: Some("$m/s$".into()),
+            ..format(1, None)
+        };
+
+        assert_eq!(format.format(-3.0), "−3.0 $m/s$");
+    }
+
+    fn number_path(scene: &Scene, number: &Handle<DecimalNumber>, time: f32) -> String {
+        let objects = scene.render_at(time, (1920.0, 1080.0)).objects;
+        match &objects[&*number.number].object_kind {
+            ObjectKind::Model(model) => to_path_data(model.path()),
+            ObjectKind::Group(_) => unreachable!("numbers are models"),
+        }
+    }
+
+    fn constant(value: f32) -> String {
+        let mut b = SceneBuilder::new();
+        let number = b.add(DecimalNumber::new(value).with_decimals(0));
+        number_path(&b.finish(), &number, 0.0)
+    }
+
+    #[test]
+    fn counts_can_be_rendered_in_any_order() {
+        let mut b = SceneBuilder::new();
+        let number = b.add(DecimalNumber::new(7.0).with_decimals(0));
+        b.play(Wait.with_duration(1.0));
+        b.play(number.count(0.0, 100.0).with_duration(1.0));
+        b.play(Wait.with_duration(1.0));
+        let scene = b.finish();
+
+        assert_eq!(number_path(&scene, &number, 2.5), constant(100.0));
+        assert_eq!(number_path(&scene, &number, 0.5), constant(7.0));
+        assert_eq!(number_path(&scene, &number, 1.5), constant(50.0));
+        assert_eq!(number_path(&scene, &number, 0.5), constant(7.0));
+    }
+}
@@ -1,9 +1,12 @@
 use egui::{pos2, Color32, Pos2, Rect};
-use lyon::path::Path;
-
-use crate::dynamics::Dynamic;
+use lyon::{
+    geom::traits::Transformation,
+    math::{point, vector, Point, Vector},
+    path::Path,
+};
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FillMaterial {
     pub color: Color32,
 }
@@ -15,6 +18,7 @@ impl FillMaterial {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StrokeMaterial {
     pub color: Color32,
     pub width: f32,
@@ -27,6 +31,7 @@ impl StrokeMaterial {
 }
 
 #[derive(Clone, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Material {
     pub fill: Option<FillMaterial>,
     pub stroke: Option<StrokeMaterial>,
@@ -52,6 +57,7 @@ impl From<StrokeMaterial> for Material {
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Transform {
     pub position: Pos2,
     pub rotation: f32,
@@ -95,6 +101,17 @@ impl Transform {
         }
     }
 
+    /// The transform that undoes this one, e.g., to map world coordinates
+    /// back into an object's local space.
+    pub fn inverse(&self) -> Self {
+        Transform {
+            position: self.anchor,
+            rotation: -self.rotation,
+            scale: 1.0 / self.scale,
+            anchor: self.position,
+        }
+    }
+
     pub fn map_aabb(&self, rect: Rect) -> Rect {
         let top_left = self.apply(rect.min);
         let top_right = self.apply(pos2(rect.max.x, rect.min.y));
@@ -128,39 +145,59 @@ impl Transform {
     }
 
     pub fn with_position(&self, position: Pos2) -> Self {
-        let mut new = self.clone();
+        let mut new = *self;
         new.position = position;
         new
     }
 
     pub fn with_rotation(&self, rotation: f32) -> Self {
-        let mut new = self.clone();
+        let mut new = *self;
         new.rotation = rotation;
         new
     }
 
     pub fn with_scale(&self, scale: f32) -> Self {
-        let mut new = self.clone();
+        let mut new = *self;
         new.scale = scale;
         new
     }
 
     pub fn with_anchor(&self, anchor: Pos2) -> Self {
-        let mut new = self.clone();
+        let mut new = *self;
         new.anchor = anchor;
         new
     }
 }
 
+impl Transformation<f32> for Transform {
+    fn transform_point(&self, p: Point) -> Point {
+        let p = self.apply(pos2(p.x, p.y));
+        point(p.x, p.y)
+    }
+
+    fn transform_vector(&self, v: Vector) -> Vector {
+        let v = Self::rotate_vec_around_0(pos2(v.x, v.y), self.rotation) * self.scale;
+        vector(v.x, v.y)
+    }
+}
+
 // TODO: At some point, we don't want Model to actually store the mesh, but rather a reference to
 // it.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Model {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialization::path_data"))]
     path: Path,
+    /// The material at full opacity. Fades scale it by
+    /// [`Model::opacity`] when the model is painted, so the colors survive
+    /// fading out completely.
     pub material: Material,
+    #[cfg_attr(feature = "serde", serde(default = "full_opacity"))]
+    opacity: f32,
 
     /// This should be updated every time the path is updated. We use this to
     /// check if the path has been updated, since paths are not hashable.
+    #[cfg_attr(feature = "serde", serde(skip))]
     path_revision: usize,
 }
 
@@ -169,6 +206,7 @@ impl Model {
         Self {
             path,
             material,
+            opacity: 1.0,
             path_revision: 0,
         }
     }
@@ -185,11 +223,42 @@ impl Model {
     pub fn revision(&self) -> usize {
         self.path_revision
     }
+
+    pub fn opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    /// Fades the model, from 0 for invisible to 1 for as opaque as its
+    /// material.
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity.clamp(0.0, 1.0);
+    }
+
+    /// The material as the model is painted, with its opacity applied.
+    pub fn painted_material(&self) -> Material {
+        let fade = |color: Color32| color.gamma_multiply(self.opacity);
+
+        Material {
+            fill: self.material.fill.as_ref().map(|fill| FillMaterial {
+                color: fade(fill.color),
+            }),
+            stroke: self.material.stroke.as_ref().map(|stroke| StrokeMaterial {
+                color: fade(stroke.color),
+                width: stroke.width,
+            }),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+fn full_opacity() -> f32 {
+    1.0
 }
 
 pub type ObjectId = usize;
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ObjectKind {
     // TODO: Maybe someday 🥲
     // Model3D(Model3D),
@@ -198,6 +267,7 @@ pub enum ObjectKind {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Object {
     pub object_kind: ObjectKind,
     pub transform: Transform,
@@ -206,11 +276,7 @@ pub struct Object {
 impl Object {
     pub fn new_model(path: Path, material: Material) -> Self {
         Self {
-            object_kind: ObjectKind::Model(Model {
-                path,
-                material,
-                path_revision: 0,
-            }),
+            object_kind: ObjectKind::Model(Model::new(path, material)),
             transform: Transform::default(),
         }
     }
@@ -53,6 +53,12 @@ pub struct RenderObject {
     pub mesh_revision: usize,
 }
 
+impl Default for ObjectTree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ObjectTree {
     pub fn new() -> Self {
         let mut objects = HashMap::new();
@@ -134,12 +140,13 @@ impl ObjectTree {
 
         match &object.object_kind {
             ObjectKind::Model(model) => {
-                if model.material.fill.is_none() && model.material.stroke.is_none() {
+                let material = model.painted_material();
+                if material.fill.is_none() && material.stroke.is_none() {
                     panic!("Tried to render object with no fill or stroke");
                 }
 
-                if let Some(fill) = &model.material.fill {
-                    let mesh = Self::tessellate_fill(fill_tessellator, &model.path());
+                if let Some(fill) = &material.fill {
+                    let mesh = Self::tessellate_fill(fill_tessellator, model.path());
 
                     if !mesh.vertices.is_empty() {
                         objects.push(RenderObject {
@@ -153,9 +160,9 @@ impl ObjectTree {
                     }
                 }
 
-                if let Some(stroke) = &model.material.stroke {
+                if let Some(stroke) = &material.stroke {
                     let mesh =
-                        Self::tessellate_stroke(stroke_tessellator, stroke.width, &model.path());
+                        Self::tessellate_stroke(stroke_tessellator, stroke.width, model.path());
 
                     if !mesh.vertices.is_empty() {
                         objects.push(RenderObject {
@@ -199,6 +206,24 @@ impl ObjectTree {
         objects
     }
 
+    /// Builds a tree from its objects, finding the parent of each object from
+    /// the groups.
+    pub fn from_objects(root: ObjectId, objects: HashMap<ObjectId, Object>) -> Self {
+        let parent_map = objects
+            .iter()
+            .flat_map(|(id, object)| match &object.object_kind {
+                ObjectKind::Group(children) => children.iter().map(|child| (*child, *id)).collect(),
+                ObjectKind::Model(_) => Vec::new(),
+            })
+            .collect();
+
+        Self {
+            root,
+            objects,
+            parent_map,
+        }
+    }
+
     pub fn add(&mut self, id: usize, object: Object, rooted: bool) {
         if let ObjectKind::Group(children) = &object.object_kind {
             for child_id in children {
@@ -221,6 +246,28 @@ impl ObjectTree {
         self.objects.insert(id, object);
     }
 
+    /// Removes the object and everything in it from the tree, and from the
+    /// group it is in.
+    pub fn remove_object(&mut self, id: ObjectId) {
+        if let Some(parent_id) = self.parent_map.remove(&id) {
+            if let Some(ObjectKind::Group(children)) = self
+                .objects
+                .get_mut(&parent_id)
+                .map(|parent| &mut parent.object_kind)
+            {
+                children.retain(|child| *child != id);
+            }
+        }
+
+        if let Some(object) = self.objects.remove(&id) {
+            if let ObjectKind::Group(children) = object.object_kind {
+                for child in children {
+                    self.remove_object(child);
+                }
+            }
+        }
+    }
+
     pub fn merge(&mut self, other: ObjectTree, root_id: ObjectId) -> Vec<ObjectId> {
         let mut rooted = Vec::new();
         self.objects.extend(
@@ -245,7 +292,9 @@ impl ObjectTree {
         rooted
     }
 
-    fn flattened_transform(&self, id: ObjectId) -> Transform {
+    /// The transform of all of the object's parents, which maps the space its
+    /// own transform is in into world coordinates.
+    pub fn flattened_transform(&self, id: ObjectId) -> Transform {
         let mut curr_id = id;
         let mut transforms = Vec::new();
 
@@ -264,6 +313,13 @@ impl ObjectTree {
             })
     }
 
+    /// The transform that maps the object's local coordinates into world
+    /// coordinates, i.e., its own transform with all of its parents' applied.
+    pub fn world_transform(&self, id: ObjectId) -> Transform {
+        let object = self.objects.get(&id).unwrap();
+        self.flattened_transform(id).and_then(&object.transform)
+    }
+
     fn bounding_box_with_transform(&self, object: &Object, transform: Transform) -> Rect {
         let transform = transform.and_then(&object.transform);
 
@@ -286,11 +342,13 @@ impl ObjectTree {
         }
     }
 
-    // pub fn bounding_box(&self, id: ObjectId) -> Box2D {
-    //     let transform = self.flattened_transform(id);
-    //     let object = self.objects.get(&id).unwrap();
-    //     self.bounding_box_with_transform(object, transform)
-    // }
+    /// The bounding box of the object in world coordinates, i.e., with all of
+    /// its parents' transforms applied.
+    pub fn bounding_box(&self, id: ObjectId) -> Rect {
+        let transform = self.flattened_transform(id);
+        let object = self.objects.get(&id).unwrap();
+        self.bounding_box_with_transform(object, transform)
+    }
 
     /// This is the bounding box of the object in its local coordinate system.
     /// i.e., the bounding box without any of its parents' transforms applied.
@@ -338,3 +396,153 @@ impl ObjectTree {
         boxes
     }
 }
+
+/// Objects are written sorted by id, so the same tree is always written the
+/// same way, and can be diffed. Since ids are random, they are renumbered in
+/// the order the objects are drawn in, starting with the root at 0; objects
+/// that aren't in the tree under the root are left out. The parents are not
+/// written, since the groups already say what they are.
+#[cfg(feature = "serde")]
+impl serde::Serialize for ObjectTree {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(serde::Serialize)]
+        struct Tree {
+            root: ObjectId,
+            objects: std::collections::BTreeMap<ObjectId, Object>,
+        }
+
+        fn number(tree: &ObjectTree, id: ObjectId, ids: &mut HashMap<ObjectId, ObjectId>) {
+            ids.insert(id, ids.len());
+            if let Some(ObjectKind::Group(children)) =
+                tree.objects.get(&id).map(|object| &object.object_kind)
+            {
+                for child in children {
+                    number(tree, *child, ids);
+                }
+            }
+        }
+
+        let mut ids = HashMap::new();
+        number(self, self.root, &mut ids);
+
+        let objects = ids
+            .iter()
+            .filter_map(|(id, stable_id)| {
+                let mut object = self.objects.get(id)?.clone();
+                if let ObjectKind::Group(children) = &mut object.object_kind {
+                    for child in children {
+                        *child = ids[child];
+                    }
+                }
+                Some((*stable_id, object))
+            })
+            .collect();
+
+        serde::Serialize::serialize(
+            &Tree {
+                root: ids[&self.root],
+                objects,
+            },
+            serializer,
+        )
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ObjectTree {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        struct Tree {
+            root: ObjectId,
+            objects: HashMap<ObjectId, Object>,
+        }
+
+        let tree = <Tree as serde::Deserialize>::deserialize(deserializer)?;
+        Ok(Self::from_objects(tree.root, tree.objects))
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use crate::{
+        builder::Builder,
+        group::Group,
+        object::{FillMaterial, Model},
+        scene::{SceneBuilder, Snapshot},
+        shapes::Circle,
+    };
+    use egui::{pos2, Color32};
+
+    fn snapshot() -> Snapshot {
+        let mut b = SceneBuilder::new();
+        let mut group = Group::new();
+        for radius in [1.0, 2.0] {
+            group.add(Circle {
+                radius,
+                material: FillMaterial::new(Color32::WHITE).into(),
+            });
+        }
+        b.add(group.with_transform(Transform::default().with_position(pos2(3.0, 1.0))));
+        b.add(Circle {
+            radius: 0.5,
+            material: FillMaterial::new(Color32::RED).into(),
+        });
+
+        b.finish().render_at(0.0, (1920.0, 1080.0))
+    }
+
+    #[test]
+    fn trees_are_written_the_same_way_every_time() {
+        let first = serde_json::to_string(&snapshot().objects).unwrap();
+        let second = serde_json::to_string(&snapshot().objects).unwrap();
+        assert_eq!(first, second);
+
+        // The ids are numbered in drawing order and written in that order.
+        let count = snapshot().objects.objects.len();
+        let positions: Vec<_> = (0..count)
+            .map(|id| first.find(&format!("\"{id}\":")).unwrap())
+            .collect();
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(!first.contains(&format!("\"{count}\":")));
+    }
+
+    #[test]
+    fn trees_round_trip() {
+        let objects = snapshot().objects;
+        let json = serde_json::to_string(&objects).unwrap();
+        let parsed: ObjectTree = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(serde_json::to_string(&parsed).unwrap(), json);
+        assert_eq!(parsed.objects.len(), objects.objects.len());
+
+        // The parents are found again from the groups.
+        let mut boxes: Vec<_> = objects.bounding_boxes().into_values().collect();
+        let mut parsed_boxes: Vec<_> = parsed.bounding_boxes().into_values().collect();
+        let key = |rect: &Rect| [rect.min.x, rect.min.y, rect.max.x, rect.max.y];
+        boxes.sort_by(|a, b| key(a).partial_cmp(&key(b)).unwrap());
+        parsed_boxes.sort_by(|a, b| key(a).partial_cmp(&key(b)).unwrap());
+        assert_eq!(parsed_boxes, boxes);
+        let moved = |tree: &ObjectTree| {
+            tree.objects
+                .keys()
+                .filter(|id| tree.world_transform(**id).position == pos2(3.0, 1.0))
+                .count()
+        };
+        assert_eq!(moved(&parsed), moved(&objects));
+        assert!(moved(&parsed) > 1);
+    }
+
+    #[test]
+    fn path_revisions_are_not_written() {
+        let mut model = Model::new(Path::new(), FillMaterial::new(Color32::WHITE).into());
+        model.update_path(Path::new());
+        assert_eq!(model.revision(), 1);
+
+        let json = serde_json::to_string(&model).unwrap();
+        assert!(!json.contains("revision"), "{json}");
+        let parsed: Model = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.revision(), 0);
+        assert_eq!(parsed.opacity(), 1.0);
+    }
+}
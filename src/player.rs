@@ -0,0 +1,167 @@
+use egui::{Color32, Stroke};
+use enimate::{
+    camera::Camera, registry::SceneRegistry, renderer::Renderer, scene::Scene,
+    world::MOUSE_POSITION,
+};
+use std::collections::HashMap;
+
+struct App {
+    current_scene: usize,
+    scenes: SceneRegistry,
+    renderer: Renderer,
+    play: bool,
+    current_time: f32,
+    /// The camera of the last frame, to map the mouse into the scene before
+    /// the next one is rendered.
+    camera: Camera,
+    /// Outlines the bounding box of every object over the scene.
+    show_bounding_boxes: bool,
+}
+
+impl App {
+    fn scene(&self) -> &Scene {
+        self.scenes.scene(self.current_scene)
+    }
+}
+
+impl eframe::App for App {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let dt = ctx.input(|i| i.stable_dt);
+
+        if self.play && self.current_time < self.scene().length() {
+            self.current_time += dt;
+        }
+
+        if self.current_time >= self.scene().length() {
+            self.play = false;
+            self.current_time = self.scene().length();
+        }
+
+        ctx.request_repaint();
+
+        if ctx.input(|i| i.key_pressed(egui::Key::Space)) {
+            if self.current_time >= self.scene().length() {
+                self.current_time = 0.0;
+                self.play = true;
+            } else {
+                self.play = !self.play;
+            }
+        }
+
+        egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
+            egui::ComboBox::from_label("Scene")
+                .selected_text(self.scenes.entry(self.current_scene).title)
+                .show_ui(ui, |ui| {
+                    for (i, entry) in self.scenes.entries().iter().enumerate() {
+                        if ui
+                            .selectable_value(&mut self.current_scene, i, entry.title)
+                            .on_hover_text(entry.tags.join(", "))
+                            .clicked()
+                        {
+                            self.current_time = 0.0;
+                            self.play = true;
+                        }
+                    }
+                });
+            ui.checkbox(&mut self.show_bounding_boxes, "Bounding boxes");
+        });
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.label(format!("Time: {}", self.current_time));
+            if self.play {
+                if ui.button("Pause").clicked() {
+                    self.play = false;
+                }
+            } else {
+                if ui.button("Play").clicked() {
+                    self.play = true;
+                }
+            }
+
+            ui.with_layout(egui::Layout::bottom_up(egui::Align::Center), |ui| {
+                let length = self.scene().length();
+
+                ui.add(
+                    egui::Slider::new(&mut self.current_time, 0.0..=length).clamp_to_range(true),
+                );
+
+                egui::Frame::canvas(ui.style()).show(ui, |ui| {
+                    let rect = ui.available_rect_before_wrap();
+                    let _response = ui.allocate_rect(rect, egui::Sense::drag());
+
+                    let current_time = self.current_time;
+                    let size = rect.size();
+
+                    let mut input = HashMap::new();
+                    if let Some(pos) = ctx.input(|i| i.pointer.hover_pos()) {
+                        let pos = self
+                            .camera
+                            .screen_to_world((pos - rect.left_top()).to_pos2(), (size.x, size.y));
+                        input.insert(MOUSE_POSITION.id(), pos.into());
+                    }
+
+                    let snapshot =
+                        self.scene()
+                            .render_with_input(current_time, (size.x, size.y), input);
+
+                    self.camera = snapshot.camera;
+                    let boxes = self
+                        .show_bounding_boxes
+                        .then(|| snapshot.objects.bounding_boxes());
+
+                    self.renderer.paint_at(ui, rect, snapshot);
+
+                    if let Some(boxes) = boxes {
+                        let bb_canvas = ui.painter_at(rect);
+                        let view = self.camera.world_to_screen((size.x, size.y));
+                        for bb in boxes.values() {
+                            let corners = [
+                                bb.left_top(),
+                                bb.right_top(),
+                                bb.right_bottom(),
+                                bb.left_bottom(),
+                            ]
+                            .map(|corner| view.apply(corner) + rect.left_top().to_vec2())
+                            .to_vec();
+
+                            bb_canvas.add(egui::Shape::closed_line(
+                                corners,
+                                Stroke::new(1.0, Color32::RED),
+                            ));
+                        }
+                    }
+                });
+            });
+        });
+    }
+}
+
+impl App {
+    fn new<'a>(cc: &'a eframe::CreationContext<'a>, scenes: SceneRegistry) -> Self {
+        let renderer = Renderer::new(cc).unwrap();
+
+        Self {
+            current_scene: 0,
+            scenes,
+            renderer,
+            play: true,
+            current_time: 0.0,
+            camera: Camera::default(),
+            show_bounding_boxes: false,
+        }
+    }
+}
+
+/// Opens the player window, on the first scene.
+pub fn run(scenes: SceneRegistry) -> Result<(), eframe::Error> {
+    let native_options = eframe::NativeOptions {
+        renderer: eframe::Renderer::Wgpu,
+        ..Default::default()
+    };
+
+    eframe::run_native(
+        "My egui App",
+        native_options,
+        Box::new(|cc| Box::new(App::new(cc, scenes))),
+    )
+}
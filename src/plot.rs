@@ -0,0 +1,360 @@
+use egui::{pos2, Pos2};
+use lyon::{math::point, path::Path};
+
+use crate::{
+    axes::AxesHandle,
+    builder::Builder,
+    component::{Component, Handle},
+    motion::AddUpdater,
+    object::{Material, Object, ObjectId},
+    shapes::set_path,
+    world::World,
+};
+
+/// How many evenly spaced samples are taken before refining.
+const INITIAL_SAMPLES: usize = 32;
+/// How many times a sample interval can be split in half.
+const MAX_DEPTH: usize = 10;
+/// How far (in scene units) the midpoint of an interval can be from the chord
+/// before the interval is split.
+const TOLERANCE: f32 = 0.002;
+/// How sharply (in radians) the curve can turn at the midpoint of an interval
+/// before the interval is split.
+const MAX_TURN: f32 = 0.1;
+/// Intervals still longer than this (in scene units) after being split
+/// `MAX_DEPTH` times may jump over a discontinuity.
+const MAX_JUMP: f32 = 0.5;
+/// How much of such a jump has to be within one half of the interval for it
+/// to be a discontinuity.
+const JUMP_RATIO: f32 = 0.9;
+
+/// Builds a path out of sampled points, where `None` breaks the path.
+struct PolylineBuilder {
+    path_builder: lyon::path::path::Builder,
+    drawing: bool,
+}
+
+impl PolylineBuilder {
+    fn add(&mut self, sample: Option<Pos2>) {
+        match (sample, self.drawing) {
+            (Some(pos), false) => {
+                self.path_builder.begin(point(pos.x, pos.y));
+                self.drawing = true;
+            }
+            (Some(pos), true) => {
+                self.path_builder.line_to(point(pos.x, pos.y));
+            }
+            (None, true) => {
+                self.path_builder.end(false);
+                self.drawing = false;
+            }
+            (None, false) => {}
+        }
+    }
+}
+
+/// Samples `f` over `range`, splitting intervals where the curve bends and
+/// breaking the path where it is undefined (not finite) or jumps.
+pub fn sample_curve(f: impl Fn(f32) -> Pos2, range: (f32, f32)) -> Path {
+    let sample = |t: f32| {
+        let point = f(t);
+        (point.x.is_finite() && point.y.is_finite()).then_some(point)
+    };
+
+    let mut polyline = PolylineBuilder {
+        path_builder: Path::builder(),
+        drawing: false,
+    };
+
+    let (start, end) = range;
+    let step = (end - start) / INITIAL_SAMPLES as f32;
+
+    let mut previous = sample(start);
+    polyline.add(previous);
+
+    for i in 1..=INITIAL_SAMPLES {
+        let t0 = start + step * (i - 1) as f32;
+        let t1 = start + step * i as f32;
+        let current = sample(t1);
+
+        refine(&sample, t0, previous, t1, current, 0, &mut polyline);
+        polyline.add(current);
+
+        previous = current;
+    }
+
+    polyline.add(None);
+    polyline.path_builder.build()
+}
+
+/// Adds the points between `p0` and `p1` (exclusive), `None` marking a break.
+fn refine(
+    sample: &impl Fn(f32) -> Option<Pos2>,
+    t0: f32,
+    p0: Option<Pos2>,
+    t1: f32,
+    p1: Option<Pos2>,
+    depth: usize,
+    polyline: &mut PolylineBuilder,
+) {
+    let tm = (t0 + t1) / 2.0;
+    let pm = sample(tm);
+
+    let split = match (p0, pm, p1) {
+        (Some(p0), Some(pm), Some(p1)) => {
+            let chord_midpoint = p0 + (p1 - p0) / 2.0;
+            let (a, b) = (pm - p0, p1 - pm);
+            let turn = a.angle() - b.angle();
+            let turn = (turn.sin()).atan2(turn.cos()).abs();
+
+            (pm - chord_midpoint).length() > TOLERANCE
+                || (a.length() > TOLERANCE && b.length() > TOLERANCE && turn > MAX_TURN)
+        }
+        (None, None, None) => false,
+        // Part of the interval is undefined, so find where it starts.
+        _ => true,
+    };
+
+    if depth >= MAX_DEPTH || !split {
+        if let (Some(p0), Some(pm), Some(p1)) = (p0, pm, p1) {
+            // A steep but continuous curve spreads the jump over both halves,
+            // while a discontinuity keeps it within one of them.
+            let jump = (p1 - p0).length();
+            let half_jump = (pm - p0).length().max((p1 - pm).length());
+
+            if depth >= MAX_DEPTH && jump > MAX_JUMP && half_jump > jump * JUMP_RATIO {
+                polyline.add(None);
+            }
+        }
+        return;
+    }
+
+    refine(sample, t0, p0, tm, pm, depth + 1, polyline);
+    polyline.add(pm);
+    refine(sample, tm, pm, t1, p1, depth + 1, polyline);
+}
+
+/// Maps points in data coordinates into the local space of the curve's
+/// object, through the axes if the curve is plotted on any. The axes and the
+/// curve can be in different groups, so points go through wherever the axes
+/// are in the world and back through the curve's own placement.
+fn data_to_local(
+    world: &World,
+    axes: &Option<AxesHandle>,
+    object_id: ObjectId,
+) -> impl Fn(Pos2) -> Pos2 {
+    let axes = axes.as_ref().map(|axes| {
+        let axes_to_world = world.objects.world_transform(axes.x_axis.object_id);
+        let world_to_local = world.objects.world_transform(object_id).inverse();
+
+        (axes.system, world_to_local.and_then(&axes_to_world))
+    });
+
+    move |point| match &axes {
+        Some((system, transform)) => transform.apply(system.coords_to_point(point.x, point.y)),
+        None => point,
+    }
+}
+
+/// A function of the world and a parameter, evaluated every frame.
+pub type PlotFunction<T> = Box<dyn Fn(&World, f32) -> T>;
+
+/// The graph of `y = f(x)`. The function gets the world, so it can read
+/// dynamic parameters, and the graph is re-sampled every frame.
+pub struct FunctionGraph {
+    f: PlotFunction<f32>,
+    x_range: (f32, f32),
+    axes: Option<AxesHandle>,
+    material: Material,
+}
+
+#[derive(Clone)]
+pub struct FunctionGraphHandle {
+    pub graph: Handle<Object>,
+}
+
+impl FunctionGraph {
+    pub fn new(
+        f: impl Fn(f32) -> f32 + 'static,
+        x_range: (f32, f32),
+        material: impl Into<Material>,
+    ) -> Self {
+        Self::dynamic(move |_, x| f(x), x_range, material)
+    }
+
+    pub fn dynamic(
+        f: impl Fn(&World, f32) -> f32 + 'static,
+        x_range: (f32, f32),
+        material: impl Into<Material>,
+    ) -> Self {
+        Self {
+            f: Box::new(f),
+            x_range,
+            axes: None,
+            material: material.into(),
+        }
+    }
+
+    /// Plots the graph in the data space of the axes, following them as they
+    /// move. Otherwise, the graph is plotted in scene coordinates.
+    pub fn with_axes(mut self, axes: &AxesHandle) -> Self {
+        self.axes = Some(axes.clone());
+        self
+    }
+}
+
+impl Component for FunctionGraph {
+    type Handle = FunctionGraphHandle;
+
+    fn build<B: Builder>(self, builder: &mut B) -> Self::Handle {
+        let graph = builder.add(Object::new_model(Path::new(), self.material));
+
+        let object_id = *graph;
+        let FunctionGraph {
+            f, x_range, axes, ..
+        } = self;
+
+        builder.play(AddUpdater::new(move |world| {
+            let to_local = data_to_local(world, &axes, object_id);
+            let path = sample_curve(|x| to_local(pos2(x, f(world, x))), x_range);
+            set_path(world, object_id, path);
+        }));
+
+        FunctionGraphHandle { graph }
+    }
+}
+
+/// The curve traced by `f(t)` as `t` goes through `t_range`. Like
+/// [`FunctionGraph`], the curve is re-sampled every frame.
+pub struct ParametricCurve {
+    f: PlotFunction<Pos2>,
+    t_range: (f32, f32),
+    axes: Option<AxesHandle>,
+    material: Material,
+}
+
+#[derive(Clone)]
+pub struct ParametricCurveHandle {
+    pub curve: Handle<Object>,
+}
+
+impl ParametricCurve {
+    pub fn new(
+        f: impl Fn(f32) -> Pos2 + 'static,
+        t_range: (f32, f32),
+        material: impl Into<Material>,
+    ) -> Self {
+        Self::dynamic(move |_, t| f(t), t_range, material)
+    }
+
+    pub fn dynamic(
+        f: impl Fn(&World, f32) -> Pos2 + 'static,
+        t_range: (f32, f32),
+        material: impl Into<Material>,
+    ) -> Self {
+        Self {
+            f: Box::new(f),
+            t_range,
+            axes: None,
+            material: material.into(),
+        }
+    }
+
+    /// Plots the curve in the data space of the axes, following them as they
+    /// move. Otherwise, the curve is plotted in scene coordinates.
+    pub fn with_axes(mut self, axes: &AxesHandle) -> Self {
+        self.axes = Some(axes.clone());
+        self
+    }
+}
+
+impl Component for ParametricCurve {
+    type Handle = ParametricCurveHandle;
+
+    fn build<B: Builder>(self, builder: &mut B) -> Self::Handle {
+        let curve = builder.add(Object::new_model(Path::new(), self.material));
+
+        let object_id = *curve;
+        let ParametricCurve {
+            f, t_range, axes, ..
+        } = self;
+
+        builder.play(AddUpdater::new(move |world| {
+            let to_local = data_to_local(world, &axes, object_id);
+            let path = sample_curve(|t| to_local(f(world, t)), t_range);
+            set_path(world, object_id, path);
+        }));
+
+        ParametricCurveHandle { curve }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        axes::{Axes, AxisRange},
+        component::ComponentExt,
+        object::StrokeMaterial,
+        probe::SceneProbe,
+        scene::SceneBuilder,
+    };
+    use egui::{Color32, Rect};
+
+    /// Axes with a flat graph on them, built together in one component.
+    struct Plot;
+
+    struct PlotHandle {
+        axes: Handle<Axes>,
+        graph: Handle<FunctionGraph>,
+    }
+
+    impl Component for Plot {
+        type Handle = PlotHandle;
+
+        fn build<B: Builder>(self, builder: &mut B) -> PlotHandle {
+            let axes = builder.add(Axes::new(
+                AxisRange::new(-1.0, 1.0, 1.0),
+                AxisRange::new(-1.0, 1.0, 1.0),
+            ));
+            let graph = builder.add(
+                FunctionGraph::new(
+                    |_| 0.0,
+                    (-1.0, 1.0),
+                    StrokeMaterial::new(Color32::WHITE, 0.1),
+                )
+                .with_axes(&axes),
+            );
+
+            PlotHandle { axes, graph }
+        }
+    }
+
+    #[test]
+    fn graphs_follow_axes_in_a_transformed_component() {
+        let mut b = SceneBuilder::new();
+        let plot = b.add(Plot.with_position(pos2(5.0, 0.0)));
+        let probe = SceneProbe::new(b.finish());
+
+        let frame = probe.at(0.0);
+        let x_axis = frame.get(&plot.axes.x_axis).unwrap().bounds;
+        let graph = frame.get(&plot.graph.graph).unwrap().bounds;
+
+        assert_eq!(x_axis, Rect::from_min_max(pos2(4.0, 0.0), pos2(6.0, 0.0)));
+        assert!(
+            (graph.min - x_axis.min).length() < 1e-4 && (graph.max - x_axis.max).length() < 1e-4,
+            "the graph is at {graph:?}"
+        );
+    }
+
+    #[test]
+    fn sampling_breaks_at_discontinuities() {
+        let path = sample_curve(|x| pos2(x, 1.0 / x), (-1.0, 1.0));
+        let subpaths = path
+            .iter()
+            .filter(|event| matches!(event, lyon::path::Event::Begin { .. }))
+            .count();
+
+        assert_eq!(subpaths, 2);
+    }
+}
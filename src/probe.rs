@@ -0,0 +1,359 @@
+use egui::{Pos2, Rect};
+use std::fmt;
+
+use crate::{
+    component::{Component, Handle},
+    object::{Material, ObjectId, ObjectKind, Transform},
+    object_tree::ObjectTree,
+    scene::{Scene, Snapshot},
+    timing::Time,
+};
+
+/// Evaluates a scene at any time to check what its objects look like, e.g.,
+/// in tests. Objects are looked up by the handles they were added with, which
+/// stay valid however many times the scene is evaluated.
+///
+/// ```
+/// use egui::{pos2, Color32};
+/// use enimate::{
+///     builder::Builder, motion::Motion, object::FillMaterial, probe::SceneProbe,
+///     scene::SceneBuilder, shapes::Circle,
+/// };
+///
+/// let mut b = SceneBuilder::new();
+/// let circle = b.add(Circle {
+///     radius: 1.0,
+///     material: FillMaterial::new(Color32::WHITE).into(),
+/// });
+/// b.play(circle.move_to(pos2(2.0, 0.0)).with_duration(1.0));
+///
+/// let probe = SceneProbe::new(b.finish());
+/// probe.assert_position(&circle, 1.0, pos2(2.0, 0.0));
+/// ```
+///
+/// Every query evaluates the scene again at its time. To check several
+/// things at the same time, evaluate it once with [`SceneProbe::at`].
+pub struct SceneProbe {
+    scene: Scene,
+    render_size: (f32, f32),
+    tolerance: f32,
+}
+
+impl SceneProbe {
+    pub fn new(scene: Scene) -> Self {
+        Self {
+            scene,
+            render_size: (1920.0, 1080.0),
+            tolerance: 1e-3,
+        }
+    }
+
+    /// The size the scene is evaluated at, which matters for anything laid
+    /// out relative to the frame.
+    pub fn with_render_size(mut self, width: f32, height: f32) -> Self {
+        self.render_size = (width, height);
+        self
+    }
+
+    /// How far off a value may be and still pass the assertions, in scene
+    /// units, radians, or opacity.
+    pub fn with_tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn scene(&self) -> &Scene {
+        &self.scene
+    }
+
+    /// Evaluates the scene at `time`, to look up any number of objects in.
+    pub fn at(&self, time: Time) -> ProbeFrame {
+        ProbeFrame {
+            time,
+            snapshot: self.scene.render_at(time, self.render_size),
+        }
+    }
+
+    /// The object at `time`, or `None` if it isn't in the scene then.
+    pub fn object<C: Component>(&self, handle: &Handle<C>, time: Time) -> Option<ProbedObject> {
+        self.at(time).get(handle)
+    }
+
+    #[track_caller]
+    fn expect<C: Component>(&self, handle: &Handle<C>, time: Time) -> ProbedObject {
+        match self.object(handle, time) {
+            Some(object) => object,
+            None => panic!("object {} is not in the scene at {time}s", handle.object_id),
+        }
+    }
+
+    #[track_caller]
+    fn check(
+        &self,
+        what: &str,
+        handle_id: ObjectId,
+        time: Time,
+        pass: bool,
+        details: fmt::Arguments,
+    ) {
+        assert!(
+            pass,
+            "{what} of object {handle_id} at {time}s: {details} (tolerance {})",
+            self.tolerance
+        );
+    }
+
+    /// Asserts that the origin of the object is at `expected` at `time`.
+    #[track_caller]
+    pub fn assert_position<C: Component>(&self, handle: &Handle<C>, time: Time, expected: Pos2) {
+        let actual = self.expect(handle, time).position;
+        self.check(
+            "position",
+            handle.object_id,
+            time,
+            actual.distance(expected) <= self.tolerance,
+            format_args!("expected {expected:?}, got {actual:?}"),
+        );
+    }
+
+    #[track_caller]
+    pub fn assert_rotation<C: Component>(&self, handle: &Handle<C>, time: Time, expected: f32) {
+        let actual = self.expect(handle, time).transform.rotation;
+        self.check(
+            "rotation",
+            handle.object_id,
+            time,
+            (actual - expected).abs() <= self.tolerance,
+            format_args!("expected {expected}, got {actual}"),
+        );
+    }
+
+    #[track_caller]
+    pub fn assert_bounds<C: Component>(&self, handle: &Handle<C>, time: Time, expected: Rect) {
+        let actual = self.expect(handle, time).bounds;
+        let close = [
+            (actual.min.x, expected.min.x),
+            (actual.min.y, expected.min.y),
+            (actual.max.x, expected.max.x),
+            (actual.max.y, expected.max.y),
+        ]
+        .iter()
+        .all(|(actual, expected)| (actual - expected).abs() <= self.tolerance);
+
+        self.check(
+            "bounds",
+            handle.object_id,
+            time,
+            close,
+            format_args!("expected {expected:?}, got {actual:?}"),
+        );
+    }
+
+    /// Colors are stored with 8 bits of alpha, so opacities are only ever
+    /// within 1 / 255 of what was set, which is allowed for whatever the
+    /// tolerance.
+    #[track_caller]
+    pub fn assert_opacity<C: Component>(&self, handle: &Handle<C>, time: Time, expected: f32) {
+        let actual = self.expect(handle, time).opacity;
+        self.check(
+            "opacity",
+            handle.object_id,
+            time,
+            (actual - expected).abs() <= self.tolerance.max(1.0 / 255.0),
+            format_args!("expected {expected}, got {actual}"),
+        );
+    }
+
+    /// Asserts whether the object is in the scene at `time`, e.g., before and
+    /// after it is added.
+    #[track_caller]
+    pub fn assert_present<C: Component>(&self, handle: &Handle<C>, time: Time, present: bool) {
+        let actual = self.at(time).contains(handle);
+        self.check(
+            "presence",
+            handle.object_id,
+            time,
+            actual == present,
+            format_args!("expected {present}, got {actual}"),
+        );
+    }
+}
+
+/// The state of a scene at one time.
+pub struct ProbeFrame {
+    pub time: Time,
+    pub snapshot: Snapshot,
+}
+
+impl ProbeFrame {
+    pub fn contains<C: Component>(&self, handle: &Handle<C>) -> bool {
+        self.snapshot.objects.contains_key(&handle.object_id)
+    }
+
+    pub fn get<C: Component>(&self, handle: &Handle<C>) -> Option<ProbedObject> {
+        self.get_by_id(handle.object_id)
+    }
+
+    pub fn get_by_id(&self, id: ObjectId) -> Option<ProbedObject> {
+        let objects = &self.snapshot.objects;
+        if !objects.contains_key(&id) {
+            return None;
+        }
+
+        let transform = objects.world_transform(id);
+        let mut materials = Vec::new();
+        collect_materials(objects, id, &mut materials);
+
+        let opacity = materials
+            .iter()
+            .flat_map(|material| {
+                let fill = material.fill.as_ref().map(|fill| fill.color);
+                let stroke = material.stroke.as_ref().map(|stroke| stroke.color);
+                fill.into_iter().chain(stroke)
+            })
+            .map(|color| color.a() as f32 / 255.0)
+            .fold(0.0, f32::max);
+
+        Some(ProbedObject {
+            id,
+            transform,
+            position: transform.apply(Pos2::ZERO),
+            bounds: objects.bounding_box(id),
+            materials,
+            opacity,
+        })
+    }
+}
+
+/// An object as it is at some time, in world coordinates, i.e., with the
+/// transforms of its parents applied.
+#[derive(Clone, Debug)]
+pub struct ProbedObject {
+    pub id: ObjectId,
+    pub transform: Transform,
+    /// Where its origin is, like [`PositionOf`](crate::tracking::PositionOf).
+    pub position: Pos2,
+    pub bounds: Rect,
+    /// The materials of its models as they are painted, i.e., faded, in the
+    /// order they are drawn in.
+    pub materials: Vec<Material>,
+    /// The highest opacity of its fills and strokes, from 0 to 1.
+    pub opacity: f32,
+}
+
+impl ProbedObject {
+    /// The material of the object, or of its first model for groups.
+    pub fn material(&self) -> Option<&Material> {
+        self.materials.first()
+    }
+}
+
+fn collect_materials(objects: &ObjectTree, id: ObjectId, materials: &mut Vec<Material>) {
+    match &objects[&id].object_kind {
+        ObjectKind::Model(model) => materials.push(model.painted_material()),
+        ObjectKind::Group(children) => {
+            for child in children {
+                collect_materials(objects, *child, materials);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        builder::Builder, group::Group, motion::Motion, object::FillMaterial, scene::SceneBuilder,
+        shapes::Circle,
+    };
+    use egui::{pos2, Color32};
+
+    fn circle(color: Color32) -> Circle {
+        Circle {
+            radius: 1.0,
+            material: FillMaterial::new(color).into(),
+        }
+    }
+
+    /// A circle that moves to (2, 0) and fades out over the first second,
+    /// and another one that is added after it.
+    fn probe() -> (SceneProbe, Handle<Circle>, Handle<Circle>) {
+        let mut b = SceneBuilder::new();
+        let moving = b.add(circle(Color32::WHITE));
+        b.play(moving.move_to(pos2(2.0, 0.0)).with_duration(1.0));
+        b.play(moving.fade_out().with_duration(1.0));
+        let late = b.add(circle(Color32::RED));
+
+        (SceneProbe::new(b.finish()), moving, late)
+    }
+
+    #[test]
+    fn assertions_pass_for_what_the_scene_shows() {
+        let (probe, moving, late) = probe();
+
+        probe.assert_position(&moving, 0.5, pos2(1.0, 0.0));
+        probe.assert_rotation(&moving, 0.5, 0.0);
+        probe.assert_bounds(
+            &moving,
+            1.0,
+            Rect::from_min_max(pos2(1.0, -1.0), pos2(3.0, 1.0)),
+        );
+        probe.assert_opacity(&moving, 1.5, 0.5);
+        probe.assert_present(&late, 1.0, false);
+        probe.assert_present(&late, 2.0, true);
+    }
+
+    #[test]
+    #[should_panic(expected = "position of object")]
+    fn position_assertions_fail() {
+        let (probe, moving, _) = probe();
+        probe.assert_position(&moving, 0.5, pos2(2.0, 0.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "bounds of object")]
+    fn bounds_assertions_fail() {
+        let (probe, moving, _) = probe();
+        probe.assert_bounds(
+            &moving,
+            0.0,
+            Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0)),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "opacity of object")]
+    fn opacity_assertions_fail() {
+        let (probe, moving, _) = probe();
+        probe.assert_opacity(&moving, 2.0, 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "presence of object")]
+    fn presence_assertions_fail() {
+        let (probe, _, late) = probe();
+        probe.assert_present(&late, 0.5, true);
+    }
+
+    #[test]
+    #[should_panic(expected = "is not in the scene")]
+    fn assertions_on_missing_objects_fail() {
+        let (probe, _, late) = probe();
+        probe.assert_position(&late, 0.5, Pos2::ZERO);
+    }
+
+    #[test]
+    fn groups_are_as_opaque_as_their_most_opaque_model() {
+        let mut b = SceneBuilder::new();
+        let mut group = Group::new();
+        group.add(circle(Color32::from_rgba_unmultiplied(255, 255, 255, 51)));
+        group.add(circle(Color32::from_rgba_unmultiplied(255, 0, 0, 204)));
+        let group = b.add(group);
+
+        let probe = SceneProbe::new(b.finish());
+        let object = probe.object(&group, 0.0).unwrap();
+        assert_eq!(object.materials.len(), 2);
+        assert!((object.opacity - 0.8).abs() < 1e-6);
+        probe.assert_opacity(&group, 0.0, 0.8);
+    }
+}
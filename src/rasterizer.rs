@@ -0,0 +1,199 @@
+use egui::{Color32, Pos2};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+use tiny_skia::{FillRule, Paint, PathBuilder, Pixmap};
+
+use crate::{
+    camera::{Affine2, Camera},
+    object_tree::RenderObject,
+    scene::Snapshot,
+};
+
+/// An image in RGBA, 8 bits per channel, with straight (not premultiplied)
+/// alpha, row by row from the top left.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>,
+}
+
+impl Image {
+    pub fn pixel(&self, x: u32, y: u32) -> Color32 {
+        let i = (y * self.width + x) as usize * 4;
+        let [r, g, b, a] = self.data[i..i + 4] else {
+            unreachable!()
+        };
+        Color32::from_rgba_unmultiplied(r, g, b, a)
+    }
+
+    pub fn write_png(&self, writer: impl Write) -> Result<(), png::EncodingError> {
+        let mut encoder = png::Encoder::new(writer, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.data)?;
+        writer.finish()
+    }
+
+    pub fn save_png(&self, path: impl AsRef<Path>) -> Result<(), png::EncodingError> {
+        self.write_png(BufWriter::new(File::create(path)?))
+    }
+
+    /// Reads the first frame of a PNG, of any color type, as RGBA.
+    pub fn read_png(reader: impl Read) -> Result<Self, png::DecodingError> {
+        let mut decoder = png::Decoder::new(reader);
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+
+        let mut reader = decoder.read_info()?;
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buffer)?;
+        buffer.truncate(info.buffer_size());
+
+        let data = match info.color_type {
+            png::ColorType::Rgba => buffer,
+            png::ColorType::Rgb => buffer
+                .chunks_exact(3)
+                .flat_map(|rgb| [rgb[0], rgb[1], rgb[2], 255])
+                .collect(),
+            png::ColorType::GrayscaleAlpha => buffer
+                .chunks_exact(2)
+                .flat_map(|gray| [gray[0], gray[0], gray[0], gray[1]])
+                .collect(),
+            // Palettes are expanded to RGB or RGBA, so this is grayscale.
+            _ => buffer
+                .iter()
+                .flat_map(|gray| [*gray, *gray, *gray, 255])
+                .collect(),
+        };
+
+        Ok(Self {
+            width: info.width,
+            height: info.height,
+            data,
+        })
+    }
+
+    pub fn load_png(path: impl AsRef<Path>) -> Result<Self, png::DecodingError> {
+        Self::read_png(BufReader::new(File::open(path)?))
+    }
+}
+
+/// Renders frames on the CPU, for when there is no GPU, e.g., when exporting
+/// or in tests. It draws the same meshes as the GPU renderer of the player,
+/// so both show the same thing, up to anti-aliasing.
+#[derive(Clone, Debug)]
+pub struct Rasterizer {
+    width: u32,
+    height: u32,
+    background: Color32,
+    anti_alias: bool,
+}
+
+impl Rasterizer {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            background: Color32::BLACK,
+            anti_alias: true,
+        }
+    }
+
+    /// Use [`Color32::TRANSPARENT`] for images with a transparent background.
+    pub fn with_background(mut self, background: Color32) -> Self {
+        self.background = background;
+        self
+    }
+
+    /// Without anti-aliasing, edges are jagged, but every pixel is either
+    /// covered or not, which makes images easier to compare.
+    pub fn with_anti_alias(mut self, anti_alias: bool) -> Self {
+        self.anti_alias = anti_alias;
+        self
+    }
+
+    pub fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    pub fn render(&self, snapshot: &Snapshot) -> Image {
+        self.render_objects(&snapshot.objects.render(), &snapshot.camera)
+    }
+
+    pub fn render_objects(&self, objects: &[RenderObject], camera: &Camera) -> Image {
+        let Some(mut pixmap) = Pixmap::new(self.width, self.height) else {
+            return Image {
+                width: self.width,
+                height: self.height,
+                data: Vec::new(),
+            };
+        };
+
+        let [r, g, b, a] = self.background.to_srgba_unmultiplied();
+        pixmap.fill(tiny_skia::Color::from_rgba8(r, g, b, a));
+
+        let view = camera.world_to_screen((self.width as f32, self.height as f32));
+        for object in objects {
+            self.draw(&mut pixmap, object, &view);
+        }
+
+        let data = pixmap
+            .pixels()
+            .iter()
+            .flat_map(|pixel| {
+                let pixel = pixel.demultiply();
+                [pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]
+            })
+            .collect();
+
+        Image {
+            width: self.width,
+            height: self.height,
+            data,
+        }
+    }
+
+    /// Fills all the triangles of the mesh as a single path, so there are no
+    /// seams where they meet.
+    fn draw(&self, pixmap: &mut Pixmap, object: &RenderObject, view: &Affine2) {
+        let transform = Affine2::from(object.transform).then(view);
+        let points: Vec<Pos2> = object
+            .mesh
+            .vertices
+            .iter()
+            .map(|vertex| transform.apply(vertex.pos))
+            .collect();
+
+        let mut builder = PathBuilder::new();
+        for triangle in object.mesh.indices.chunks_exact(3) {
+            let [a, b, c] = [0, 1, 2].map(|i| points[triangle[i] as usize]);
+            builder.move_to(a.x, a.y);
+            builder.line_to(b.x, b.y);
+            builder.line_to(c.x, c.y);
+            builder.close();
+        }
+
+        // Meshes whose triangles are all degenerate have no path.
+        let Some(path) = builder.finish() else {
+            return;
+        };
+
+        let [r, g, b, a] = object.color.to_srgba_unmultiplied();
+        let mut paint = Paint::default();
+        paint.set_color_rgba8(r, g, b, a);
+        paint.anti_alias = self.anti_alias;
+
+        pixmap.fill_path(
+            &path,
+            &paint,
+            FillRule::Winding,
+            tiny_skia::Transform::identity(),
+            None,
+        );
+    }
+}
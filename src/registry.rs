@@ -0,0 +1,108 @@
+use once_cell::unsync::OnceCell;
+
+use crate::scene::Scene;
+
+#[doc(hidden)]
+pub use inventory;
+
+/// A scene that can be picked by name, in the player or on the command line.
+/// Declare it next to the function that builds it with [`register_scene!`].
+#[derive(Clone, Copy, Debug)]
+pub struct SceneEntry {
+    pub title: &'static str,
+    /// The size to render at, unless asked for another.
+    pub resolution: (u32, u32),
+    pub tags: &'static [&'static str],
+    pub build: fn() -> Scene,
+}
+
+impl SceneEntry {
+    pub const fn new(title: &'static str, build: fn() -> Scene) -> Self {
+        Self {
+            title,
+            resolution: (1920, 1080),
+            tags: &[],
+            build,
+        }
+    }
+
+    pub const fn with_resolution(mut self, width: u32, height: u32) -> Self {
+        self.resolution = (width, height);
+        self
+    }
+
+    pub const fn with_tags(mut self, tags: &'static [&'static str]) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags
+            .iter()
+            .any(|other| other.eq_ignore_ascii_case(tag))
+    }
+}
+
+inventory::collect!(SceneEntry);
+
+/// Adds a scene to the [`SceneRegistry`], from anywhere in the program:
+///
+/// ```ignore
+/// register_scene!(SceneEntry::new("Shapes", shapes).with_tags(&["shapes"]));
+///
+/// fn shapes() -> Scene { ... }
+/// ```
+#[macro_export]
+macro_rules! register_scene {
+    ($entry:expr) => {
+        $crate::registry::inventory::submit! { $entry }
+    };
+}
+
+/// Every registered scene, sorted by title. Scenes are only built the first
+/// time they are asked for, since some, e.g., with a lot of
+/// [`Typst`](crate::typst::Typst), take a while.
+pub struct SceneRegistry {
+    entries: Vec<&'static SceneEntry>,
+    scenes: Vec<OnceCell<Scene>>,
+}
+
+impl SceneRegistry {
+    pub fn collect() -> Self {
+        let mut entries: Vec<&'static SceneEntry> = inventory::iter::<SceneEntry>().collect();
+        entries.sort_by_key(|entry| entry.title);
+
+        Self {
+            scenes: entries.iter().map(|_| OnceCell::new()).collect(),
+            entries,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn entries(&self) -> &[&'static SceneEntry] {
+        &self.entries
+    }
+
+    pub fn entry(&self, index: usize) -> &'static SceneEntry {
+        self.entries[index]
+    }
+
+    /// The index of the scene titled `title`, ignoring case.
+    pub fn find(&self, title: &str) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|entry| entry.title.eq_ignore_ascii_case(title))
+    }
+
+    /// Builds the scene the first time it is asked for.
+    pub fn scene(&self, index: usize) -> &Scene {
+        self.scenes[index].get_or_init(self.entries[index].build)
+    }
+}
@@ -1,5 +1,7 @@
-use crate::mesh::{Mesh, Vertex};
+use crate::camera::Camera;
+use crate::mesh::Vertex;
 use crate::object_tree::{ObjectTree, RenderObject, RenderObjectKind};
+use crate::scene::Snapshot;
 use eframe::wgpu::ColorTargetState;
 use eframe::{
     egui_wgpu::{self, wgpu},
@@ -9,7 +11,7 @@ use egui::{Color32, Rect, Rgba};
 use rand::Rng;
 use std::collections::HashMap;
 
-pub const UNIT_GRID_HEIGHT: f32 = 8.0;
+pub use crate::camera::UNIT_GRID_HEIGHT;
 
 struct RendererResourceManager(pub HashMap<usize, RendererResources>);
 
@@ -48,10 +50,7 @@ impl Renderer {
         Some(Self { id })
     }
 
-    fn init<'a>(
-        device: &'a wgpu::Device,
-        custom_targets: Option<ColorTargetState>,
-    ) -> RendererResources {
+    fn init(device: &wgpu::Device, custom_targets: Option<ColorTargetState>) -> RendererResources {
         let shader = device.create_shader_module(wgpu::include_wgsl!("shader.wgsl"));
 
         // Since we don't know the screen size yet, we don't initialize the camera buffer yet
@@ -122,7 +121,11 @@ impl Renderer {
                 module: &shader,
                 entry_point: "fs_main",
                 targets: &(if let Some(targets) = custom_targets {
-                    [Some(targets)]
+                    [Some(ColorTargetState {
+                        // Colors are premultiplied, see `MaterialGpu`.
+                        blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                        ..targets
+                    })]
                 } else {
                     // FIXME::::::
                     [None]
@@ -155,6 +158,7 @@ impl Renderer {
 struct RendererCallback {
     id: usize,
     world: ObjectTree,
+    camera: Camera,
     render_size: egui::Vec2,
 }
 
@@ -188,7 +192,7 @@ impl egui_wgpu::CallbackTrait for RendererCallback {
     ) -> Vec<wgpu::CommandBuffer> {
         let resources = self.get_mut_resources(cb_resources);
 
-        resources.prepare(device, queue, &self.world, self.render_size);
+        resources.prepare(device, queue, &self.world, &self.camera, self.render_size);
         Vec::new()
     }
 
@@ -204,7 +208,7 @@ impl egui_wgpu::CallbackTrait for RendererCallback {
 }
 
 impl Renderer {
-    pub fn paint_at(&mut self, ui: &mut egui::Ui, rect: Rect, world: ObjectTree) {
+    pub fn paint_at(&mut self, ui: &mut egui::Ui, rect: Rect, snapshot: Snapshot) {
         let render_size = rect.size() / ui.ctx().pixels_per_point();
 
         ui.painter_at(rect)
@@ -212,7 +216,8 @@ impl Renderer {
                 rect,
                 RendererCallback {
                     id: self.id,
-                    world,
+                    world: snapshot.objects,
+                    camera: snapshot.camera,
                     render_size,
                 },
             ));
@@ -257,6 +262,8 @@ struct LoadedMesh {
     pub material_buffer: wgpu::Buffer,
     pub bind_group: wgpu::BindGroup,
     pub revision: usize,
+    pub vertex_count: usize,
+    pub index_count: usize,
 }
 
 #[derive(Hash, Eq, PartialEq, Copy, Clone, Debug)]
@@ -292,7 +299,7 @@ impl RendererResources {
         let material_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some(&format!("Material Buffer: {}", object.id)),
             // No need for padding, since material is 16 bytes aligned.
-            contents: bytemuck::bytes_of(&MaterialGpu::from(object.color.clone())),
+            contents: bytemuck::bytes_of(&MaterialGpu::from(object.color)),
             usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::UNIFORM,
         });
 
@@ -320,6 +327,8 @@ impl RendererResources {
                 material_buffer,
                 bind_group,
                 revision: object.mesh_revision,
+                vertex_count: object.mesh.vertices.len(),
+                index_count: object.mesh.indices.len(),
             },
         );
     }
@@ -329,9 +338,6 @@ impl RendererResources {
     }
 
     fn update_mesh(queue: &wgpu::Queue, object: &RenderObject, loaded_objects: &LoadedMesh) {
-        // FIXME: Might have a bigger size than the vertex buffer, so
-        //        just double check that the buffer can grow, which I don't
-        //        think it can.
         queue.write_buffer(
             &loaded_objects.vertex_buffer,
             0,
@@ -350,7 +356,7 @@ impl RendererResources {
         queue.write_buffer(
             &loaded_objects.material_buffer,
             0,
-            bytemuck::bytes_of(&MaterialGpu::from(object.color.clone())),
+            bytemuck::bytes_of(&MaterialGpu::from(object.color)),
         );
     }
 
@@ -359,23 +365,11 @@ impl RendererResources {
         device: &wgpu::Device,
         queue: &wgpu::Queue,
         world: &ObjectTree,
+        camera: &Camera,
         render_size: egui::Vec2,
     ) {
-        let transform: Vec<f32> = vec![
-            (render_size.y / render_size.x) * (1.0 / UNIT_GRID_HEIGHT) * 2.0,
-            0.0,
-            0.0,
-            -(1.0 / UNIT_GRID_HEIGHT) * 2.0,
-        ];
-        queue.write_buffer(
-            &self.camera_buffer,
-            0,
-            // bytemuck::cast_slice(&vec![1.0, 0.0, 0.0, -0.998993]),
-            // bytemuck::cast_slice(&vec![1.0, 0.0, 0.0, -248.25 / 248.25]),
-            // bytemuck::cast_slice(&transform),
-            // bytemuck::cast_slice(&vec![100.0 / render_size.x, 0.0, 0.0, -100.0 / render_size.y]),
-            bytemuck::cast_slice(&transform),
-        );
+        let view = camera.world_to_clip((render_size.x, render_size.y));
+        queue.write_buffer(&self.camera_buffer, 0, bytemuck::bytes_of(&view));
 
         // FIXME: Bad performance, since it is updating the entire buffer every
         //        frame. Should hashing be used to determine if the buffer
@@ -386,7 +380,12 @@ impl RendererResources {
             }
 
             if let Some(loaded_object) = self.loaded_meshes.get(&MeshId(object.kind, object.id)) {
-                if loaded_object.revision != object.mesh_revision {
+                // Buffers can't grow, so a mesh whose size changed has to be
+                // reloaded even if the revision didn't.
+                if loaded_object.revision != object.mesh_revision
+                    || loaded_object.vertex_count != object.mesh.vertices.len()
+                    || loaded_object.index_count != object.mesh.indices.len()
+                {
                     self.delete_mesh(MeshId(object.kind, object.id));
                     self.load_render_object(device, object);
                 } else {
@@ -1,12 +1,13 @@
 use crate::{
     animation::Animation,
     builder::Builder,
+    camera::{Camera, CameraHandle},
     dynamics::{DynamicObject, OwnedDynamic},
     motion::{AddObject, Alpha, Motion},
-    object::{Object, ObjectId},
+    object::ObjectId,
     object_tree::ObjectTree,
     timing::{Sequence, Time},
-    world::{Variable, World},
+    world::{Var, Variable, VariableType, VariableValue, World},
 };
 use std::collections::HashMap;
 
@@ -21,36 +22,65 @@ impl Scene {
         self.0.duration()
     }
 
+    /// How far into the scene `time` is, from 0 to 1. A scene without any
+    /// duration, e.g., of still objects only, is over as soon as it starts.
     pub fn time_to_alpha(&self, time: Time) -> Alpha {
+        if self.length() == 0.0 {
+            return 1.0;
+        }
+
         time / self.length()
     }
 
-    pub fn render_at(&self, time: Time, render_size: (f32, f32)) -> ObjectTree {
-        let mut world = World::new(ObjectTree::new(), render_size, HashMap::new());
-
-        self.0.animate(&mut world, self.time_to_alpha(time));
-
-        world.objects
+    pub fn render_at(&self, time: Time, render_size: (f32, f32)) -> Snapshot {
+        self.render_with_input(time, render_size, HashMap::new())
     }
 
+    /// Renders the scene with some of its variables, e.g., inputs such as
+    /// [`MOUSE_POSITION`](crate::world::MOUSE_POSITION), set from outside.
     pub fn render_with_input(
-        &mut self,
-        time: f32,
+        &self,
+        time: Time,
         render_size: (f32, f32),
-        input: HashMap<Variable, f32>,
-    ) -> ObjectTree {
-        let mut world = World::new(ObjectTree::new(), render_size, input);
+        input: HashMap<Variable, VariableValue>,
+    ) -> Snapshot {
+        self.render_world(World::new(ObjectTree::new(), render_size, input).with_time(time, time))
+    }
+
+    /// Renders the scene at the time of the given world, which is otherwise
+    /// empty.
+    pub fn render_world(&self, mut world: World) -> Snapshot {
+        let alpha = self.time_to_alpha(world.time());
 
-        self.0.animate(&mut world, self.time_to_alpha(time));
+        self.0.animate(&mut world, alpha);
+        world.run_updaters();
 
-        world.objects
+        Snapshot {
+            objects: world.objects,
+            camera: world.camera,
+        }
     }
 }
 
+/// A rendered frame of a scene: its objects, and the camera to show them
+/// through.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Snapshot {
+    pub objects: ObjectTree,
+    pub camera: Camera,
+}
+
 pub struct SceneBuilder {
     scene: Scene,
 }
 
+impl Default for SceneBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl SceneBuilder {
     pub fn new() -> Self {
         Self {
@@ -58,6 +88,21 @@ impl SceneBuilder {
         }
     }
 
+    /// Declares a variable that motions can set and tween, and that can be
+    /// used anywhere a dynamic value is expected. The variable is set to its
+    /// initial value at this point of the scene, so it shows up in
+    /// [`World::variables`] like any other.
+    pub fn variable<T: VariableType>(&mut self, initial: T) -> Var<T> {
+        let variable = Var::new(initial);
+        self.play(variable.set(initial));
+        variable
+    }
+
+    /// The camera of the scene, for motions that pan, zoom and rotate it.
+    pub fn camera(&self) -> CameraHandle {
+        CameraHandle
+    }
+
     pub fn finish(self) -> Scene {
         self.scene
     }
@@ -0,0 +1,219 @@
+use lyon::{
+    math::point,
+    path::{Event, Path},
+};
+use std::{fmt, fmt::Write};
+
+/// Writes a path as SVG path data, e.g., `M0 0L1 2Z`, with absolute commands
+/// only. Numbers are written as precisely as needed for
+/// [`parse_path_data`] to read back the exact same path.
+pub fn to_path_data(path: &Path) -> String {
+    write_path_data(path, |value| value.to_string())
+}
+
+/// Like [`to_path_data`], but with the numbers formatted by `number`, e.g.,
+/// rounded to keep files small.
+pub fn write_path_data(path: &Path, number: impl Fn(f32) -> String) -> String {
+    let mut data = String::new();
+
+    for event in path.iter() {
+        let _ = match event {
+            Event::Begin { at } => write!(data, "M{} {}", number(at.x), number(at.y)),
+            Event::Line { to, .. } => write!(data, "L{} {}", number(to.x), number(to.y)),
+            Event::Quadratic { ctrl, to, .. } => write!(
+                data,
+                "Q{} {} {} {}",
+                number(ctrl.x),
+                number(ctrl.y),
+                number(to.x),
+                number(to.y)
+            ),
+            Event::Cubic {
+                ctrl1, ctrl2, to, ..
+            } => write!(
+                data,
+                "C{} {} {} {} {} {}",
+                number(ctrl1.x),
+                number(ctrl1.y),
+                number(ctrl2.x),
+                number(ctrl2.y),
+                number(to.x),
+                number(to.y)
+            ),
+            Event::End { close: true, .. } => write!(data, "Z"),
+            Event::End { close: false, .. } => Ok(()),
+        };
+    }
+
+    data
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PathDataError(String);
+
+impl fmt::Display for PathDataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid path data: {}", self.0)
+    }
+}
+
+impl std::error::Error for PathDataError {}
+
+/// Reads SVG path data with the absolute `M`, `L`, `Q`, `C` and `Z`
+/// commands, which is everything [`to_path_data`] writes.
+pub fn parse_path_data(data: &str) -> Result<Path, PathDataError> {
+    let mut builder = Path::builder();
+    let mut open = false;
+    let mut rest = data.trim_start();
+
+    while let Some(command) = rest.chars().next() {
+        rest = rest[command.len_utf8()..].trim_start();
+
+        let arguments = match command {
+            'M' | 'L' => 2,
+            'Q' => 4,
+            'C' => 6,
+            'Z' => 0,
+            _ => return Err(PathDataError(format!("unsupported command {command:?}"))),
+        };
+
+        let mut numbers = [0.0; 6];
+        for number in &mut numbers[..arguments] {
+            let end = rest
+                .find(|c: char| c.is_whitespace() || c == ',' || "MLQCZ".contains(c))
+                .unwrap_or(rest.len());
+            *number = rest[..end]
+                .parse()
+                .map_err(|_| PathDataError(format!("expected a number at {rest:?}")))?;
+            rest = rest[end..].trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        }
+
+        let [a, b, c, d, e, f] = numbers;
+        match command {
+            'M' => {
+                if open {
+                    builder.end(false);
+                }
+                builder.begin(point(a, b));
+                open = true;
+                continue;
+            }
+            'Z' if open => builder.close(),
+            _ if !open => return Err(PathDataError(format!("{command:?} must come after a move"))),
+            'L' => {
+                builder.line_to(point(a, b));
+            }
+            'Q' => {
+                builder.quadratic_bezier_to(point(a, b), point(c, d));
+            }
+            'C' => {
+                builder.cubic_bezier_to(point(a, b), point(c, d), point(e, f));
+            }
+            _ => unreachable!(),
+        }
+
+        open = command != 'Z';
+    }
+
+    if open {
+        builder.end(false);
+    }
+
+    Ok(builder.build())
+}
+
+/// Serializes a [`Path`] as path data, for `#[serde(with = "...")]`.
+#[cfg(feature = "serde")]
+pub mod path_data {
+    use lyon::path::Path;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(path: &Path, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&super::to_path_data(path))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Path, D::Error> {
+        let data = String::deserialize(deserializer)?;
+        super::parse_path_data(&data).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn events(path: &Path) -> Vec<Event<lyon::math::Point, lyon::math::Point>> {
+        path.iter().collect()
+    }
+
+    fn assert_round_trips(path: &Path) {
+        let data = to_path_data(path);
+        let parsed = parse_path_data(&data).unwrap();
+        assert_eq!(events(&parsed), events(path), "through {data:?}");
+    }
+
+    #[test]
+    fn curves_round_trip() {
+        let mut builder = Path::builder();
+        builder.begin(point(0.1, -0.2));
+        builder.line_to(point(1.0 / 3.0, 2.0));
+        builder.quadratic_bezier_to(point(1e-7, 3.5), point(-4.25, 1e6));
+        builder.cubic_bezier_to(
+            point(0.3, 0.6),
+            point(0.7, -0.9),
+            point(f32::MAX, f32::MIN_POSITIVE),
+        );
+        builder.close();
+
+        assert_round_trips(&builder.build());
+    }
+
+    #[test]
+    fn open_and_closed_subpaths_round_trip() {
+        let mut builder = Path::builder();
+        builder.begin(point(0.0, 0.0));
+        builder.line_to(point(1.0, 0.0));
+        builder.end(false);
+        builder.begin(point(2.0, 0.0));
+        builder.line_to(point(3.0, 1.0));
+        builder.line_to(point(2.0, 1.0));
+        builder.close();
+        builder.begin(point(5.0, 5.0));
+        builder.cubic_bezier_to(point(6.0, 5.0), point(6.0, 6.0), point(5.0, 6.0));
+        builder.end(false);
+        let path = builder.build();
+
+        assert_eq!(to_path_data(&path), "M0 0L1 0M2 0L3 1L2 1ZM5 5C6 5 6 6 5 6");
+        assert_round_trips(&path);
+    }
+
+    #[test]
+    fn empty_paths_round_trip() {
+        assert_eq!(to_path_data(&Path::new()), "");
+        assert_round_trips(&Path::new());
+    }
+
+    #[test]
+    fn numbers_can_be_formatted() {
+        let mut builder = Path::builder();
+        builder.begin(point(1.0 / 3.0, -0.0));
+        builder.end(false);
+
+        let data = write_path_data(&builder.build(), |value| format!("{value:.2}"));
+        assert_eq!(data, "M0.33 -0.00");
+    }
+
+    #[test]
+    fn other_separators_are_read() {
+        let path = parse_path_data(" M 0,1 L2 , 3 Z ").unwrap();
+        assert_eq!(to_path_data(&path), "M0 1L2 3Z");
+    }
+
+    #[test]
+    fn invalid_data_is_rejected() {
+        assert!(parse_path_data("M0 0 A1 1 0 0 1 2 2").is_err());
+        assert!(parse_path_data("L1 1").is_err());
+        assert!(parse_path_data("M0").is_err());
+        assert!(parse_path_data("M0 x").is_err());
+    }
+}
@@ -8,6 +8,7 @@ struct VertexOutput {
 
 struct Camera {
   view_proj: mat2x2<f32>,
+  offset: vec2<f32>,
 };
 
 struct Transform {
@@ -37,7 +38,7 @@ fn vs_main(
     let scaled_pos = rotated_pos * transform.scale;
     let translated_pos = scaled_pos + transform.position + transform.anchor;
 
-    let pos = camera.view_proj * translated_pos;
+    let pos = camera.view_proj * translated_pos + camera.offset;
 
     out.clip_position = vec4<f32>(pos, 0.0, 1.0);
     return out;
@@ -1,14 +1,18 @@
-use egui::{pos2, Pos2};
+use egui::{pos2, Pos2, Vec2};
 use lyon::{
-    math::point,
-    path::{Path, Winding},
+    geom::{self, Angle, QuadraticBezierSegment},
+    math::{point, vector, Box2D, Point},
+    path::{builder::BorderRadii, Path, Winding},
 };
+use std::f32::consts::{FRAC_PI_2, TAU};
 
 use crate::{
     builder::Builder,
     component::{Component, Handle},
-    motion::Motion,
-    object::{Material, Object, ObjectId, ObjectKind, Transform},
+    dynamics::{Dynamic, OwnedDynamic},
+    interpolation::Interpolatable,
+    motion::{AddUpdater, Alpha, Motion},
+    object::{FillMaterial, Material, Object, ObjectId, ObjectKind, StrokeMaterial},
     world::World,
 };
 
@@ -17,20 +21,42 @@ pub struct Circle {
     pub material: Material,
 }
 
+#[derive(Clone)]
 pub struct CircleHandle {
-    radius: f32,
+    pub circle: Handle<Object>,
+    pub radius: f32,
+}
+
+impl Circle {
+    pub fn build_path(radius: f32) -> Path {
+        let mut path_builder = Path::builder();
+        path_builder.add_circle(point(0.0, 0.0), radius, Winding::Positive);
+
+        path_builder.build()
+    }
 }
 
 impl Component for Circle {
-    type Handle = Handle<Object>;
+    type Handle = CircleHandle;
 
     fn build<B: Builder>(self, builder: &mut B) -> Self::Handle {
-        let mut path_builder = Path::builder();
-        path_builder.add_circle(point(0.0, 0.0), self.radius, Winding::Positive);
+        let object = Object::new_model(Circle::build_path(self.radius), self.material);
+
+        CircleHandle {
+            circle: builder.add(object),
+            radius: self.radius,
+        }
+    }
+}
 
-        let object = Object::new_model(path_builder.build(), self.material);
+impl CircleHandle {
+    pub fn animate(&mut self, radius: f32) -> impl Motion {
+        let from = self.radius;
+        self.radius = radius;
 
-        builder.add(object)
+        animate_path(&self.circle, move |alpha| {
+            Circle::build_path(from.interpolate(&radius, alpha))
+        })
     }
 }
 
@@ -74,41 +100,1067 @@ impl Component for Line {
     }
 }
 
-impl Handle<Line> {
-    pub fn animate(&self, start: Option<Pos2>, end: Option<Pos2>) -> impl Motion {
-        struct LineAnimation {
-            object_id: Handle<Object>,
-            from_start: Pos2,
-            from_end: Pos2,
-            to_start: Option<Pos2>,
-            to_end: Option<Pos2>,
+impl LineHandle {
+    pub fn animate(&mut self, start: Option<Pos2>, end: Option<Pos2>) -> impl Motion {
+        let from_start = self.start;
+        let from_end = self.end;
+        let to_start = start.unwrap_or(from_start);
+        let to_end = end.unwrap_or(from_end);
+        (self.start, self.end) = (to_start, to_end);
+
+        animate_path(&self.line, move |alpha| {
+            Line::build_path(
+                from_start.interpolate(&to_start, alpha),
+                from_end.interpolate(&to_end, alpha),
+            )
+        })
+    }
+}
+
+pub(crate) fn set_path(world: &mut World, object_id: ObjectId, path: Path) {
+    match &mut world.objects.get_mut(&object_id).unwrap().object_kind {
+        ObjectKind::Model(ref mut model) => {
+            model.update_path(path);
+        }
+        _ => unreachable!(),
+    }
+}
+
+/// Builds a motion that replaces the path of a shape's model every frame with
+/// the path returned by `build_path` for the current alpha.
+pub(crate) fn animate_path(
+    model: &Handle<Object>,
+    build_path: impl Fn(Alpha) -> Path + 'static,
+) -> impl Motion {
+    struct PathAnimation<F: Fn(Alpha) -> Path + 'static> {
+        object_id: ObjectId,
+        build_path: F,
+    }
+
+    impl<F: Fn(Alpha) -> Path + 'static> Motion for PathAnimation<F> {
+        fn animate(&self, world: &mut World, alpha: Alpha) {
+            set_path(world, self.object_id, (self.build_path)(alpha));
+        }
+    }
+
+    PathAnimation {
+        object_id: **model,
+        build_path,
+    }
+}
+
+fn lerp_option(from: f32, to: Option<f32>, alpha: Alpha) -> f32 {
+    from.interpolate(&to.unwrap_or(from), alpha)
+}
+
+pub(crate) fn to_point(pos: Pos2) -> Point {
+    point(pos.x, pos.y)
+}
+
+pub struct Rectangle {
+    pub size: Vec2,
+    pub material: Material,
+}
+
+#[derive(Clone)]
+pub struct RectangleHandle {
+    pub rectangle: Handle<Object>,
+    pub size: Vec2,
+}
+
+impl Rectangle {
+    pub fn build_path(size: Vec2) -> Path {
+        let mut path_builder = Path::builder();
+        path_builder.add_rectangle(
+            &Box2D::new(
+                point(-size.x / 2.0, -size.y / 2.0),
+                point(size.x / 2.0, size.y / 2.0),
+            ),
+            Winding::Positive,
+        );
+
+        path_builder.build()
+    }
+}
+
+impl Component for Rectangle {
+    type Handle = RectangleHandle;
+
+    fn build<B: Builder>(self, builder: &mut B) -> Self::Handle {
+        let object = Object::new_model(Rectangle::build_path(self.size), self.material);
+
+        RectangleHandle {
+            rectangle: builder.add(object),
+            size: self.size,
+        }
+    }
+}
+
+impl RectangleHandle {
+    pub fn animate(&mut self, size: Vec2) -> impl Motion {
+        let from = self.size;
+        self.size = size;
+
+        animate_path(&self.rectangle, move |alpha| {
+            Rectangle::build_path(from + (size - from) * alpha)
+        })
+    }
+}
+
+pub struct RoundedRect {
+    pub size: Vec2,
+    pub corner_radius: f32,
+    pub material: Material,
+}
+
+#[derive(Clone)]
+pub struct RoundedRectHandle {
+    pub rectangle: Handle<Object>,
+    pub size: Vec2,
+    pub corner_radius: f32,
+}
+
+impl RoundedRect {
+    pub fn build_path(size: Vec2, corner_radius: f32) -> Path {
+        // Corners can't be rounder than half of the shortest side.
+        let corner_radius = corner_radius.min(size.x / 2.0).min(size.y / 2.0).max(0.0);
+
+        let mut path_builder = Path::builder();
+        path_builder.add_rounded_rectangle(
+            &Box2D::new(
+                point(-size.x / 2.0, -size.y / 2.0),
+                point(size.x / 2.0, size.y / 2.0),
+            ),
+            &BorderRadii::new(corner_radius),
+            Winding::Positive,
+        );
+
+        path_builder.build()
+    }
+}
+
+impl Component for RoundedRect {
+    type Handle = RoundedRectHandle;
+
+    fn build<B: Builder>(self, builder: &mut B) -> Self::Handle {
+        let path = RoundedRect::build_path(self.size, self.corner_radius);
+
+        RoundedRectHandle {
+            rectangle: builder.add(Object::new_model(path, self.material)),
+            size: self.size,
+            corner_radius: self.corner_radius,
+        }
+    }
+}
+
+impl RoundedRectHandle {
+    pub fn animate(&mut self, size: Option<Vec2>, corner_radius: Option<f32>) -> impl Motion {
+        let from_size = self.size;
+        let to_size = size.unwrap_or(from_size);
+        let from_corner_radius = self.corner_radius;
+        self.size = to_size;
+        self.corner_radius = corner_radius.unwrap_or(from_corner_radius);
+
+        animate_path(&self.rectangle, move |alpha| {
+            RoundedRect::build_path(
+                from_size + (to_size - from_size) * alpha,
+                lerp_option(from_corner_radius, corner_radius, alpha),
+            )
+        })
+    }
+}
+
+pub struct Ellipse {
+    pub radii: Vec2,
+    pub material: Material,
+}
+
+#[derive(Clone)]
+pub struct EllipseHandle {
+    pub ellipse: Handle<Object>,
+    pub radii: Vec2,
+}
+
+impl Ellipse {
+    pub fn build_path(radii: Vec2) -> Path {
+        let mut path_builder = Path::builder();
+        path_builder.add_ellipse(
+            point(0.0, 0.0),
+            vector(radii.x, radii.y),
+            Angle::zero(),
+            Winding::Positive,
+        );
+
+        path_builder.build()
+    }
+}
+
+impl Component for Ellipse {
+    type Handle = EllipseHandle;
+
+    fn build<B: Builder>(self, builder: &mut B) -> Self::Handle {
+        let object = Object::new_model(Ellipse::build_path(self.radii), self.material);
+
+        EllipseHandle {
+            ellipse: builder.add(object),
+            radii: self.radii,
+        }
+    }
+}
+
+impl EllipseHandle {
+    pub fn animate(&mut self, radii: Vec2) -> impl Motion {
+        let from = self.radii;
+        self.radii = radii;
+
+        animate_path(&self.ellipse, move |alpha| {
+            Ellipse::build_path(from + (radii - from) * alpha)
+        })
+    }
+}
+
+fn add_arc(
+    path_builder: &mut lyon::path::path::Builder,
+    radius: f32,
+    start_angle: f32,
+    sweep_angle: f32,
+) {
+    let arc = geom::Arc {
+        center: point(0.0, 0.0),
+        radii: vector(radius, radius),
+        start_angle: Angle::radians(start_angle),
+        sweep_angle: Angle::radians(sweep_angle),
+        x_rotation: Angle::zero(),
+    };
+
+    arc.for_each_cubic_bezier(&mut |segment| {
+        path_builder.cubic_bezier_to(segment.ctrl1, segment.ctrl2, segment.to);
+    });
+}
+
+/// An open circular arc, angles are in radians.
+pub struct Arc {
+    pub radius: f32,
+    pub start_angle: f32,
+    pub sweep_angle: f32,
+    pub material: Material,
+}
+
+#[derive(Clone)]
+pub struct ArcHandle {
+    pub arc: Handle<Object>,
+    pub radius: f32,
+    pub start_angle: f32,
+    pub sweep_angle: f32,
+}
+
+impl Arc {
+    pub fn build_path(radius: f32, start_angle: f32, sweep_angle: f32) -> Path {
+        let (sin, cos) = start_angle.sin_cos();
+
+        let mut path_builder = Path::builder();
+        path_builder.begin(point(cos * radius, sin * radius));
+        add_arc(&mut path_builder, radius, start_angle, sweep_angle);
+        path_builder.end(false);
+
+        path_builder.build()
+    }
+}
+
+impl Component for Arc {
+    type Handle = ArcHandle;
+
+    fn build<B: Builder>(self, builder: &mut B) -> Self::Handle {
+        let path = Arc::build_path(self.radius, self.start_angle, self.sweep_angle);
+
+        ArcHandle {
+            arc: builder.add(Object::new_model(path, self.material)),
+            radius: self.radius,
+            start_angle: self.start_angle,
+            sweep_angle: self.sweep_angle,
         }
+    }
+}
 
-        impl Motion for LineAnimation {
-            fn animate(&self, world: &mut World, alpha: f32) {
-                let to_start = self.to_start.unwrap_or(self.from_start);
-                let to_end = self.to_end.unwrap_or(self.from_end);
+impl ArcHandle {
+    pub fn animate(
+        &mut self,
+        radius: Option<f32>,
+        start_angle: Option<f32>,
+        sweep_angle: Option<f32>,
+    ) -> impl Motion {
+        let from = (self.radius, self.start_angle, self.sweep_angle);
+        self.radius = radius.unwrap_or(from.0);
+        self.start_angle = start_angle.unwrap_or(from.1);
+        self.sweep_angle = sweep_angle.unwrap_or(from.2);
 
-                let animated_start = self.from_start + (to_start - self.from_start) * alpha;
-                let animated_end = self.from_end + (to_end - self.from_end) * alpha;
+        animate_path(&self.arc, move |alpha| {
+            Arc::build_path(
+                lerp_option(from.0, radius, alpha),
+                lerp_option(from.1, start_angle, alpha),
+                lerp_option(from.2, sweep_angle, alpha),
+            )
+        })
+    }
+}
 
-                let path = Line::build_path(animated_start, animated_end);
+/// A closed "pie slice" of a circle, angles are in radians.
+pub struct Sector {
+    pub radius: f32,
+    pub start_angle: f32,
+    pub sweep_angle: f32,
+    pub material: Material,
+}
+
+#[derive(Clone)]
+pub struct SectorHandle {
+    pub sector: Handle<Object>,
+    pub radius: f32,
+    pub start_angle: f32,
+    pub sweep_angle: f32,
+}
+
+impl Sector {
+    pub fn build_path(radius: f32, start_angle: f32, sweep_angle: f32) -> Path {
+        let (sin, cos) = start_angle.sin_cos();
+
+        let mut path_builder = Path::builder();
+        path_builder.begin(point(0.0, 0.0));
+        path_builder.line_to(point(cos * radius, sin * radius));
+        add_arc(&mut path_builder, radius, start_angle, sweep_angle);
+        path_builder.close();
+
+        path_builder.build()
+    }
+}
+
+impl Component for Sector {
+    type Handle = SectorHandle;
+
+    fn build<B: Builder>(self, builder: &mut B) -> Self::Handle {
+        let path = Sector::build_path(self.radius, self.start_angle, self.sweep_angle);
+
+        SectorHandle {
+            sector: builder.add(Object::new_model(path, self.material)),
+            radius: self.radius,
+            start_angle: self.start_angle,
+            sweep_angle: self.sweep_angle,
+        }
+    }
+}
+
+impl SectorHandle {
+    pub fn animate(
+        &mut self,
+        radius: Option<f32>,
+        start_angle: Option<f32>,
+        sweep_angle: Option<f32>,
+    ) -> impl Motion {
+        let from = (self.radius, self.start_angle, self.sweep_angle);
+        self.radius = radius.unwrap_or(from.0);
+        self.start_angle = start_angle.unwrap_or(from.1);
+        self.sweep_angle = sweep_angle.unwrap_or(from.2);
 
-                match &mut world.objects.get_mut(&self.object_id).unwrap().object_kind {
-                    ObjectKind::Model(ref mut model) => {
-                        model.update_path(path);
-                    }
-                    _ => unreachable!(),
-                }
+        animate_path(&self.sector, move |alpha| {
+            Sector::build_path(
+                lerp_option(from.0, radius, alpha),
+                lerp_option(from.1, start_angle, alpha),
+                lerp_option(from.2, sweep_angle, alpha),
+            )
+        })
+    }
+}
+
+pub struct Polygon {
+    pub points: Vec<Pos2>,
+    pub material: Material,
+}
+
+#[derive(Clone)]
+pub struct PolygonHandle {
+    pub polygon: Handle<Object>,
+    pub points: Vec<Pos2>,
+}
+
+impl Polygon {
+    pub fn build_path(points: &[Pos2]) -> Path {
+        let points: Vec<_> = points.iter().copied().map(to_point).collect();
+
+        let mut path_builder = Path::builder();
+        path_builder.add_polygon(lyon::path::Polygon {
+            points: &points,
+            closed: true,
+        });
+
+        path_builder.build()
+    }
+}
+
+impl Component for Polygon {
+    type Handle = PolygonHandle;
+
+    fn build<B: Builder>(self, builder: &mut B) -> Self::Handle {
+        let object = Object::new_model(Polygon::build_path(&self.points), self.material);
+
+        PolygonHandle {
+            polygon: builder.add(object),
+            points: self.points,
+        }
+    }
+}
+
+impl PolygonHandle {
+    /// Morphs the polygon into another one, which may have a different number
+    /// of vertices.
+    pub fn animate(&mut self, points: Vec<Pos2>) -> impl Motion {
+        let from = Polygon::build_path(&self.points);
+        let to = Polygon::build_path(&points);
+        self.points = points;
+
+        animate_path(&self.polygon, move |alpha| from.interpolate(&to, alpha))
+    }
+}
+
+/// A polygon with `sides` equal sides, with its first vertex pointing up.
+pub struct RegularPolygon {
+    pub sides: usize,
+    pub radius: f32,
+    pub material: Material,
+}
+
+#[derive(Clone)]
+pub struct RegularPolygonHandle {
+    pub polygon: Handle<Object>,
+    pub sides: usize,
+    pub radius: f32,
+}
+
+impl RegularPolygon {
+    pub fn vertices(sides: usize, radius: f32) -> Vec<Pos2> {
+        (0..sides)
+            .map(|i| {
+                let angle = i as f32 / sides as f32 * TAU - FRAC_PI_2;
+                pos2(angle.cos() * radius, angle.sin() * radius)
+            })
+            .collect()
+    }
+
+    pub fn build_path(sides: usize, radius: f32) -> Path {
+        Polygon::build_path(&RegularPolygon::vertices(sides, radius))
+    }
+}
+
+impl Component for RegularPolygon {
+    type Handle = RegularPolygonHandle;
+
+    fn build<B: Builder>(self, builder: &mut B) -> Self::Handle {
+        let path = RegularPolygon::build_path(self.sides, self.radius);
+
+        RegularPolygonHandle {
+            polygon: builder.add(Object::new_model(path, self.material)),
+            sides: self.sides,
+            radius: self.radius,
+        }
+    }
+}
+
+impl RegularPolygonHandle {
+    /// Morphs the number of sides and the radius of the polygon.
+    pub fn animate(&mut self, sides: Option<usize>, radius: Option<f32>) -> impl Motion {
+        let from = RegularPolygon::build_path(self.sides, self.radius);
+        self.sides = sides.unwrap_or(self.sides);
+        self.radius = radius.unwrap_or(self.radius);
+        let to = RegularPolygon::build_path(self.sides, self.radius);
+
+        animate_path(&self.polygon, move |alpha| from.interpolate(&to, alpha))
+    }
+}
+
+/// A star with `points` tips, alternating between the outer and inner radius.
+pub struct Star {
+    pub points: usize,
+    pub outer_radius: f32,
+    pub inner_radius: f32,
+    pub material: Material,
+}
+
+#[derive(Clone)]
+pub struct StarHandle {
+    pub star: Handle<Object>,
+    pub points: usize,
+    pub outer_radius: f32,
+    pub inner_radius: f32,
+}
+
+impl Star {
+    pub fn vertices(points: usize, outer_radius: f32, inner_radius: f32) -> Vec<Pos2> {
+        (0..points * 2)
+            .map(|i| {
+                let radius = if i % 2 == 0 {
+                    outer_radius
+                } else {
+                    inner_radius
+                };
+                let angle = i as f32 / (points * 2) as f32 * TAU - FRAC_PI_2;
+                pos2(angle.cos() * radius, angle.sin() * radius)
+            })
+            .collect()
+    }
+
+    pub fn build_path(points: usize, outer_radius: f32, inner_radius: f32) -> Path {
+        Polygon::build_path(&Star::vertices(points, outer_radius, inner_radius))
+    }
+}
+
+impl Component for Star {
+    type Handle = StarHandle;
+
+    fn build<B: Builder>(self, builder: &mut B) -> Self::Handle {
+        let path = Star::build_path(self.points, self.outer_radius, self.inner_radius);
+
+        StarHandle {
+            star: builder.add(Object::new_model(path, self.material)),
+            points: self.points,
+            outer_radius: self.outer_radius,
+            inner_radius: self.inner_radius,
+        }
+    }
+}
+
+impl StarHandle {
+    /// Morphs the number of tips and the radii of the star.
+    pub fn animate(
+        &mut self,
+        points: Option<usize>,
+        outer_radius: Option<f32>,
+        inner_radius: Option<f32>,
+    ) -> impl Motion {
+        let from = Star::build_path(self.points, self.outer_radius, self.inner_radius);
+        self.points = points.unwrap_or(self.points);
+        self.outer_radius = outer_radius.unwrap_or(self.outer_radius);
+        self.inner_radius = inner_radius.unwrap_or(self.inner_radius);
+        let to = Star::build_path(self.points, self.outer_radius, self.inner_radius);
+
+        animate_path(&self.star, move |alpha| from.interpolate(&to, alpha))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ArrowHead {
+    /// A filled triangle.
+    Triangle,
+    /// A filled triangle with a notch cut out of its back.
+    Stealth,
+    /// Two stroked lines meeting at the tip.
+    Open,
+}
+
+/// An arrow between two dynamic points, so it stays attached to whatever the
+/// points follow. The endpoints are in the arrow's coordinate space, and the
+/// arrow is recomputed after every motion of the frame has been applied.
+pub struct Arrow {
+    pub start: OwnedDynamic<Pos2>,
+    pub end: OwnedDynamic<Pos2>,
+    pub stroke: StrokeMaterial,
+    pub head: ArrowHead,
+    /// The length of the head in scene units.
+    pub head_size: f32,
+    pub double: bool,
+    pub bend: f32,
+}
+
+#[derive(Clone)]
+pub struct ArrowHandle {
+    pub shaft: Handle<Object>,
+    /// The head at the end, followed by the one at the start if the arrow is
+    /// double-headed.
+    pub heads: Vec<Handle<Object>>,
+}
+
+impl Arrow {
+    pub fn new(start: impl Dynamic<Pos2>, end: impl Dynamic<Pos2>, stroke: StrokeMaterial) -> Self {
+        Self {
+            start: OwnedDynamic::new(start),
+            end: OwnedDynamic::new(end),
+            head_size: stroke.width * 4.0,
+            stroke,
+            head: ArrowHead::Triangle,
+            double: false,
+            bend: 0.0,
+        }
+    }
+
+    pub fn with_head(mut self, head: ArrowHead) -> Self {
+        self.head = head;
+        self
+    }
+
+    /// The length of the head in scene units, which stays the same no matter
+    /// how long the arrow is.
+    pub fn with_head_size(mut self, head_size: f32) -> Self {
+        self.head_size = head_size;
+        self
+    }
+
+    pub fn double_headed(mut self) -> Self {
+        self.double = true;
+        self
+    }
+
+    /// Curves the arrow into a quadratic bezier whose control point is pushed
+    /// away from the middle of the arrow by `bend` times its length.
+    pub fn with_bend(mut self, bend: f32) -> Self {
+        self.bend = bend;
+        self
+    }
+
+    /// Returns the path of the shaft, and the paths of the heads at the end and
+    /// (if double-headed) at the start.
+    pub fn build_paths(
+        start: Pos2,
+        end: Pos2,
+        head: ArrowHead,
+        head_size: f32,
+        double: bool,
+        bend: f32,
+    ) -> (Path, Vec<Path>) {
+        let chord = end - start;
+        let control = start + chord / 2.0 + chord.rot90() * bend;
+        let curve = QuadraticBezierSegment {
+            from: to_point(start),
+            ctrl: to_point(control),
+            to: to_point(end),
+        };
+
+        // How much of the shaft is covered by a head, so it doesn't poke out
+        // of the tip.
+        let inset = match head {
+            ArrowHead::Triangle => head_size,
+            ArrowHead::Stealth => head_size * 0.6,
+            ArrowHead::Open => 0.0,
+        };
+        let inset = inset.min(chord.length() / if double { 2.0 } else { 1.0 });
+
+        let end_t = curve_t_at_distance(&curve, inset, false);
+        let start_t = if double {
+            curve_t_at_distance(&curve, inset, true)
+        } else {
+            0.0
+        };
+
+        let shaft_curve = curve.split_range(start_t..end_t);
+        let mut path_builder = Path::builder();
+        path_builder.begin(shaft_curve.from);
+        path_builder.quadratic_bezier_to(shaft_curve.ctrl, shaft_curve.to);
+        path_builder.end(false);
+        let shaft = path_builder.build();
+
+        let mut heads = vec![Arrow::build_head(end, end - control, head, head_size)];
+        if double {
+            heads.push(Arrow::build_head(start, start - control, head, head_size));
+        }
+
+        (shaft, heads)
+    }
+
+    fn build_head(tip: Pos2, direction: Vec2, head: ArrowHead, head_size: f32) -> Path {
+        let direction = direction.normalized();
+        let normal = direction.rot90() * head_size * 0.4;
+        let back = tip - direction * head_size;
+
+        let points = match head {
+            ArrowHead::Triangle => vec![tip, back + normal, back - normal],
+            ArrowHead::Stealth => vec![
+                tip,
+                back + normal,
+                tip - direction * head_size * 0.6,
+                back - normal,
+            ],
+            ArrowHead::Open => vec![back + normal, tip, back - normal],
+        };
+        let points: Vec<_> = points.into_iter().map(to_point).collect();
+
+        let mut path_builder = Path::builder();
+        path_builder.add_polygon(lyon::path::Polygon {
+            points: &points,
+            closed: head != ArrowHead::Open,
+        });
+
+        path_builder.build()
+    }
+}
+
+/// Finds the parameter of the point on `curve` that is `distance` away from
+/// its end (or its start, if `from_start` is set).
+fn curve_t_at_distance(
+    curve: &QuadraticBezierSegment<f32>,
+    distance: f32,
+    from_start: bool,
+) -> f32 {
+    let (mut low, mut high) = (0.0, 1.0);
+    let anchor = if from_start { curve.from } else { curve.to };
+
+    for _ in 0..16 {
+        let mid = (low + high) / 2.0;
+        let t = if from_start { mid } else { 1.0 - mid };
+
+        if (curve.sample(t) - anchor).length() < distance {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+
+    let mid = (low + high) / 2.0;
+    if from_start {
+        mid
+    } else {
+        1.0 - mid
+    }
+}
+
+impl Component for Arrow {
+    type Handle = ArrowHandle;
+
+    fn build<B: Builder>(self, builder: &mut B) -> Self::Handle {
+        let head_material: Material = match self.head {
+            ArrowHead::Open => self.stroke.clone().into(),
+            _ => FillMaterial::new(self.stroke.color).into(),
+        };
+
+        let shaft = builder.add(Object::new_model(Path::new(), self.stroke.into()));
+        let heads: Vec<_> = (0..if self.double { 2 } else { 1 })
+            .map(|_| builder.add(Object::new_model(Path::new(), head_material.clone())))
+            .collect();
+
+        let shaft_id = *shaft;
+        let head_ids: Vec<_> = heads.iter().map(|head| **head).collect();
+        let Arrow {
+            start,
+            end,
+            head,
+            head_size,
+            double,
+            bend,
+            ..
+        } = self;
+
+        builder.play(AddUpdater::new(move |world| {
+            let (shaft, heads) = Arrow::build_paths(
+                start.get(world),
+                end.get(world),
+                head,
+                head_size,
+                double,
+                bend,
+            );
+
+            set_path(world, shaft_id, shaft);
+            for (head_id, head) in head_ids.iter().zip(heads) {
+                set_path(world, *head_id, head);
             }
+        }));
+
+        ArrowHandle { shaft, heads }
+    }
+}
+
+/// An arrow from the origin to a dynamic point, e.g., a vector on a number
+/// plane.
+pub struct Vector {
+    pub tip: OwnedDynamic<Pos2>,
+    pub stroke: StrokeMaterial,
+    pub head: ArrowHead,
+    /// The length of the head in scene units.
+    pub head_size: f32,
+}
+
+impl Vector {
+    pub fn new(tip: impl Dynamic<Pos2>, stroke: StrokeMaterial) -> Self {
+        Self {
+            tip: OwnedDynamic::new(tip),
+            head_size: stroke.width * 4.0,
+            stroke,
+            head: ArrowHead::Triangle,
+        }
+    }
+
+    pub fn with_head(mut self, head: ArrowHead) -> Self {
+        self.head = head;
+        self
+    }
+
+    pub fn with_head_size(mut self, head_size: f32) -> Self {
+        self.head_size = head_size;
+        self
+    }
+}
+
+impl Component for Vector {
+    type Handle = ArrowHandle;
+
+    fn build<B: Builder>(self, builder: &mut B) -> Self::Handle {
+        Arrow {
+            start: OwnedDynamic::new(Pos2::ZERO),
+            end: self.tip,
+            stroke: self.stroke,
+            head: self.head,
+            head_size: self.head_size,
+            double: false,
+            bend: 0.0,
+        }
+        .build(builder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        object::FillMaterial, probe::SceneProbe, scene::SceneBuilder, tracking::PositionOf,
+    };
+    use egui::{vec2, Color32, Rect};
+    use lyon::{algorithms::aabb::bounding_box, path::Event};
+    use std::f32::consts::PI;
+
+    fn assert_close(actual: Point, expected: Point) {
+        assert!(
+            (actual - expected).length() < 1e-4,
+            "expected {expected:?}, got {actual:?}"
+        );
+    }
+
+    fn assert_bounds(path: &Path, min: Point, max: Point) {
+        let bounds = bounding_box(path);
+        assert_close(bounds.min, min);
+        assert_close(bounds.max, max);
+    }
+
+    fn first_point(path: &Path) -> Point {
+        match path.iter().next() {
+            Some(Event::Begin { at }) => at,
+            event => panic!("expected a path, got {event:?}"),
+        }
+    }
+
+    fn last_point(path: &Path) -> Point {
+        path.iter()
+            .filter_map(|event| match event {
+                Event::Begin { .. } | Event::End { .. } => None,
+                event => Some(event.to()),
+            })
+            .last()
+            .unwrap()
+    }
+
+    fn is_closed(path: &Path) -> bool {
+        path.iter()
+            .any(|event| matches!(event, Event::End { close: true, .. }))
+    }
+
+    fn distance_to_origin(vertices: &[Pos2]) -> Vec<f32> {
+        vertices
+            .iter()
+            .map(|vertex| (vertex.to_vec2().length() * 1e4).round() / 1e4)
+            .collect()
+    }
+
+    #[test]
+    fn rectangle_is_centered() {
+        let path = Rectangle::build_path(vec2(4.0, 2.0));
+        assert_bounds(&path, point(-2.0, -1.0), point(2.0, 1.0));
+        assert!(is_closed(&path));
+    }
+
+    #[test]
+    fn rounded_rect_keeps_its_size() {
+        let path = RoundedRect::build_path(vec2(4.0, 2.0), 0.5);
+        assert_bounds(&path, point(-2.0, -1.0), point(2.0, 1.0));
+    }
+
+    #[test]
+    fn rounded_rect_clamps_the_corner_radius() {
+        // A radius of half the shortest side turns the short sides into
+        // half circles, so anything larger gives the same path.
+        let clamped = RoundedRect::build_path(vec2(4.0, 2.0), 5.0);
+        let capsule = RoundedRect::build_path(vec2(4.0, 2.0), 1.0);
+
+        assert_bounds(&clamped, point(-2.0, -1.0), point(2.0, 1.0));
+        assert_eq!(
+            clamped.iter().collect::<Vec<_>>(),
+            capsule.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn ellipse_spans_its_radii() {
+        let path = Ellipse::build_path(vec2(3.0, 1.0));
+        assert_bounds(&path, point(-3.0, -1.0), point(3.0, 1.0));
+    }
+
+    #[test]
+    fn arc_runs_from_start_to_end_angle() {
+        let path = Arc::build_path(2.0, 0.0, PI);
+
+        assert_close(first_point(&path), point(2.0, 0.0));
+        assert_close(last_point(&path), point(-2.0, 0.0));
+        assert_bounds(&path, point(-2.0, 0.0), point(2.0, 2.0));
+        assert!(!is_closed(&path));
+    }
+
+    #[test]
+    fn sector_is_a_closed_slice_from_the_center() {
+        let path = Sector::build_path(2.0, 0.0, FRAC_PI_2);
+
+        assert_close(first_point(&path), point(0.0, 0.0));
+        assert_close(last_point(&path), point(0.0, 2.0));
+        assert_bounds(&path, point(0.0, 0.0), point(2.0, 2.0));
+        assert!(is_closed(&path));
+    }
+
+    #[test]
+    fn polygon_goes_through_its_points() {
+        let points = [pos2(0.0, 0.0), pos2(2.0, 0.0), pos2(1.0, 3.0)];
+        let path = Polygon::build_path(&points);
+
+        let corners: Vec<_> = path
+            .iter()
+            .filter_map(|event| match event {
+                Event::Begin { at } => Some(at),
+                Event::Line { to, .. } => Some(to),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(corners, points.map(to_point));
+        assert!(is_closed(&path));
+    }
+
+    #[test]
+    fn regular_polygon_starts_at_the_top() {
+        let vertices = RegularPolygon::vertices(4, 2.0);
+
+        assert_eq!(vertices.len(), 4);
+        assert_close(to_point(vertices[0]), point(0.0, -2.0));
+        assert_close(to_point(vertices[1]), point(2.0, 0.0));
+        assert_eq!(distance_to_origin(&vertices), [2.0; 4]);
+    }
+
+    #[test]
+    fn star_alternates_between_its_radii() {
+        let vertices = Star::vertices(5, 2.0, 1.0);
+
+        assert_eq!(vertices.len(), 10);
+        assert_close(to_point(vertices[0]), point(0.0, -2.0));
+        assert_eq!(
+            distance_to_origin(&vertices),
+            [2.0, 1.0, 2.0, 1.0, 2.0, 1.0, 2.0, 1.0, 2.0, 1.0]
+        );
+    }
+
+    #[test]
+    fn shape_animations_blend_parameters() {
+        let mut b = SceneBuilder::new();
+        let mut rectangle = b.add(Rectangle {
+            size: vec2(2.0, 2.0),
+            material: FillMaterial::new(Color32::WHITE).into(),
+        });
+        let mut polygon = b.add(RegularPolygon {
+            sides: 3,
+            radius: 1.0,
+            material: FillMaterial::new(Color32::WHITE).into(),
+        });
+        b.play(rectangle.animate(vec2(4.0, 2.0)).with_duration(1.0));
+        b.play(polygon.animate(Some(6), Some(2.0)).with_duration(1.0));
+
+        let probe = SceneProbe::new(b.finish());
+        probe.assert_bounds(
+            &rectangle,
+            0.5,
+            Rect::from_min_max(pos2(-1.5, -1.0), pos2(1.5, 1.0)),
+        );
+        probe.assert_bounds(
+            &polygon,
+            2.0,
+            Rect::from_center_size(Pos2::ZERO, vec2(2.0 * 3f32.sqrt(), 4.0)),
+        );
+    }
+
+    #[test]
+    fn chained_animations_continue_from_the_last_one() {
+        let mut b = SceneBuilder::new();
+        let mut rectangle = b.add(Rectangle {
+            size: vec2(2.0, 2.0),
+            material: FillMaterial::new(Color32::WHITE).into(),
+        });
+        let mut star = b.add(Star {
+            points: 5,
+            outer_radius: 1.0,
+            inner_radius: 0.5,
+            material: FillMaterial::new(Color32::WHITE).into(),
+        });
+        b.play(rectangle.animate(vec2(4.0, 2.0)).with_duration(1.0));
+        b.play(rectangle.animate(vec2(4.0, 6.0)).with_duration(1.0));
+        b.play(star.animate(None, Some(2.0), None).with_duration(1.0));
+        b.play(star.animate(None, None, Some(1.5)).with_duration(1.0));
+
+        assert_eq!(rectangle.size, vec2(4.0, 6.0));
+        assert_eq!((star.outer_radius, star.inner_radius), (2.0, 1.5));
+
+        // Halfway through the second animation, the first one's width is
+        // kept rather than snapping back to the original.
+        let probe = SceneProbe::new(b.finish());
+        probe.assert_bounds(
+            &rectangle,
+            1.5,
+            Rect::from_center_size(Pos2::ZERO, vec2(4.0, 4.0)),
+        );
+        let bounds = probe.object(&star, 4.0).unwrap().bounds;
+        assert!((bounds.top() + 2.0).abs() < 1e-3, "{bounds:?}");
+    }
+
+    #[test]
+    fn arrow_heads_keep_their_size() {
+        for length in [1.0, 10.0] {
+            let (shaft, heads) = Arrow::build_paths(
+                Pos2::ZERO,
+                pos2(length, 0.0),
+                ArrowHead::Triangle,
+                0.5,
+                true,
+                0.0,
+            );
+
+            assert_bounds(&heads[0], point(length - 0.5, -0.2), point(length, 0.2));
+            assert_bounds(&heads[1], point(0.0, -0.2), point(0.5, 0.2));
+            assert_bounds(&shaft, point(0.5, 0.0), point(length - 0.5, 0.0));
         }
+    }
+
+    #[test]
+    fn vector_follows_its_tip() {
+        let mut b = SceneBuilder::new();
+        let dot = b.add(Circle {
+            radius: 0.1,
+            material: FillMaterial::new(Color32::WHITE).into(),
+        });
+        let vector = b.add(Vector::new(
+            PositionOf::new(&dot),
+            StrokeMaterial::new(Color32::WHITE, 0.1),
+        ));
+        b.play(dot.mv(pos2(3.0, 0.0), pos2(0.0, 2.0)).with_duration(1.0));
+
+        let probe = SceneProbe::new(b.finish());
+        for (time, tip) in [(0.0, pos2(3.0, 0.0)), (1.0, pos2(0.0, 2.0))] {
+            let shaft = probe.object(&vector.shaft, time).unwrap().bounds;
+            let head = probe.object(&vector.heads[0], time).unwrap().bounds;
 
-        LineAnimation {
-            object_id: self.line.clone(),
-            from_start: self.start,
-            from_end: self.end,
-            to_start: start,
-            to_end: end,
+            assert!(shaft.min.distance(Pos2::ZERO) < 1e-3);
+            assert!(head.distance_to_pos(tip) < 1e-3);
         }
     }
+
+    #[test]
+    fn circle_spans_its_radius() {
+        assert_bounds(&Circle::build_path(1.5), point(-1.5, -1.5), point(1.5, 1.5));
+    }
 }
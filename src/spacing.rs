@@ -1,4 +1,4 @@
-use crate::{dynamics::Dynamic, object::ObjectId, renderer::UNIT_GRID_HEIGHT, world::World};
+use crate::{camera::UNIT_GRID_HEIGHT, dynamics::Dynamic, object::ObjectId, world::World};
 use egui::Pos2;
 
 #[derive(Clone)]
@@ -24,11 +24,14 @@ pub enum VerticalAlignment {
     Bottom,
 }
 
+/// A point on the bounding box of an object, in world coordinates unless
+/// [`relative_to`](Alignment::relative_to) says otherwise.
 #[derive(Clone)]
 pub struct Alignment {
     target: ObjectId,
     horizontal: HorizontalAlignment,
     vertical: VerticalAlignment,
+    space: Option<ObjectId>,
 }
 
 impl Alignment {
@@ -37,9 +40,18 @@ impl Alignment {
             target,
             horizontal: HorizontalAlignment::Center,
             vertical: VerticalAlignment::Center,
+            space: None,
         })
     }
 
+    /// Maps the point into the space of `parent`, for consumers that are
+    /// positioned within it rather than at the root, e.g., motions moving a
+    /// child of a transformed group.
+    pub fn relative_to(mut self, parent: ObjectId) -> Self {
+        self.space = Some(parent);
+        self
+    }
+
     pub fn left(mut self) -> Self {
         self.horizontal = HorizontalAlignment::Left;
         self
@@ -68,7 +80,7 @@ impl Alignment {
 
 impl Dynamic<Pos2> for Alignment {
     fn get(&self, world: &World) -> Pos2 {
-        let bb = world.objects.local_bounding_box(self.target);
+        let bb = world.objects.bounding_box(self.target);
 
         let x = match self.horizontal {
             HorizontalAlignment::Left => bb.left(),
@@ -82,6 +94,64 @@ impl Dynamic<Pos2> for Alignment {
             VerticalAlignment::Bottom => bb.bottom(),
         };
 
-        Pos2::new(x, y)
+        match self.space {
+            Some(parent) => world
+                .objects
+                .world_transform(parent)
+                .inverse()
+                .apply(Pos2::new(x, y)),
+            None => Pos2::new(x, y),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        builder::Builder, group::Group, object::FillMaterial, object::Transform,
+        scene::SceneBuilder, shapes::Circle,
+    };
+    use egui::{pos2, Color32};
+    use std::collections::HashMap;
+
+    /// Where the left of a circle in a group is, with the group at `position`,
+    /// optionally in the space of the group.
+    fn left_of_circle_in_group(position: Pos2, in_group: bool) -> Pos2 {
+        let mut b = SceneBuilder::new();
+        let mut group = Group::new();
+        group.add(Circle {
+            radius: 1.0,
+            material: FillMaterial::new(Color32::WHITE).into(),
+        });
+        let group = b.add(group.with_transform(Transform::default().with_position(position)));
+
+        let snapshot = b.finish().render_at(0.0, (1920.0, 1080.0));
+        let world = World::new(snapshot.objects, (1920.0, 1080.0), HashMap::new());
+        let alignment = Alignment::new(group.children[0].object_id).left();
+        let alignment = if in_group {
+            alignment.relative_to(group.object_id)
+        } else {
+            alignment
+        };
+        alignment.get(&world)
+    }
+
+    #[test]
+    fn alignment_is_in_world_space() {
+        assert_eq!(left_of_circle_in_group(Pos2::ZERO, false), pos2(-1.0, 0.0));
+        assert_eq!(
+            left_of_circle_in_group(pos2(5.0, 2.0), false),
+            pos2(4.0, 2.0)
+        );
+    }
+
+    #[test]
+    fn alignment_can_be_relative_to_a_parent() {
+        assert_eq!(left_of_circle_in_group(Pos2::ZERO, true), pos2(-1.0, 0.0));
+        assert_eq!(
+            left_of_circle_in_group(pos2(5.0, 2.0), true),
+            pos2(-1.0, 0.0)
+        );
     }
 }
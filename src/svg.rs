@@ -0,0 +1,691 @@
+use egui::{Color32, Rect};
+use std::{collections::HashMap, fmt::Write as _, io::Write};
+
+use crate::{
+    camera::{Affine2, Camera},
+    export::{ClipSettings, ExportError, ExportSettings},
+    object::{Model, ObjectId, ObjectKind, Transform},
+    object_tree::ObjectTree,
+    scene::{Scene, Snapshot},
+    serialization::write_path_data,
+    timing::Time,
+};
+
+/// Formats a number with as few digits as needed, to keep files small.
+fn number(value: f32) -> String {
+    let text = format!("{:.4}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".into()
+    } else {
+        text.into()
+    }
+}
+
+/// An SVG color and its opacity, which SVG keeps apart.
+fn color(color: Color32) -> (String, String) {
+    let [r, g, b, a] = color.to_srgba_unmultiplied();
+    (format!("#{r:02x}{g:02x}{b:02x}"), number(a as f32 / 255.0))
+}
+
+fn matrix(transform: &Affine2) -> String {
+    let [a, b, c, d] = transform.matrix;
+    let [e, f] = transform.offset;
+    format!(
+        "matrix({} {} {} {} {} {})",
+        number(a),
+        number(b),
+        number(c),
+        number(d),
+        number(e),
+        number(f)
+    )
+}
+
+/// The paint of a model, which is all of its material that SVG needs.
+#[derive(Clone, Copy, PartialEq)]
+struct Paint {
+    fill: Option<Color32>,
+    stroke: Option<(Color32, f32)>,
+}
+
+impl From<&Model> for Paint {
+    fn from(model: &Model) -> Self {
+        let material = model.painted_material();
+
+        Self {
+            fill: material.fill.as_ref().map(|fill| fill.color),
+            stroke: material
+                .stroke
+                .as_ref()
+                .map(|stroke| (stroke.color, stroke.width)),
+        }
+    }
+}
+
+impl Paint {
+    /// The `fill` and `stroke` attributes. Fills use the even-odd rule and
+    /// strokes the SVG defaults for caps and joins, like the renderer.
+    fn attributes(&self) -> String {
+        let mut attributes = String::new();
+
+        match self.fill {
+            Some(fill) => {
+                let (fill, opacity) = color(fill);
+                let _ = write!(attributes, r#" fill="{fill}" fill-rule="evenodd""#);
+                if opacity != "1" {
+                    let _ = write!(attributes, r#" fill-opacity="{opacity}""#);
+                }
+            }
+            None => attributes.push_str(r#" fill="none""#),
+        }
+
+        if let Some((stroke, width)) = self.stroke {
+            let (stroke, opacity) = color(stroke);
+            let _ = write!(
+                attributes,
+                r#" stroke="{stroke}" stroke-width="{}""#,
+                number(width)
+            );
+            if opacity != "1" {
+                let _ = write!(attributes, r#" stroke-opacity="{opacity}""#);
+            }
+        }
+
+        attributes
+    }
+
+    /// How opaque the paint is, going by its most opaque color.
+    fn alpha(&self) -> f32 {
+        let fill = self.fill.map_or(0, |fill| fill.a());
+        let stroke = self.stroke.map_or(0, |(stroke, _)| stroke.a());
+        fill.max(stroke) as f32 / 255.0
+    }
+
+    /// Whether this is `other` faded to `opacity`, up to rounding. Colors
+    /// are compared unmultiplied, since that is how fading changes them, and
+    /// the fainter a color, the less precise its unmultiplied channels.
+    fn is_faded(&self, other: &Paint, opacity: f32) -> bool {
+        let faded = |color: Color32, other: Color32| {
+            let [r, g, b, a] = color.to_srgba_unmultiplied();
+            let [other_r, other_g, other_b, other_a] = other.to_srgba_unmultiplied();
+            let tolerance = 2 + 255 / a.max(1) as u16;
+
+            (a as f32 - other_a as f32 * opacity).abs() <= 2.0
+                && (r.abs_diff(other_r) as u16) <= tolerance
+                && (g.abs_diff(other_g) as u16) <= tolerance
+                && (b.abs_diff(other_b) as u16) <= tolerance
+        };
+
+        let fills = match (self.fill, other.fill) {
+            (Some(fill), Some(other)) => faded(fill, other),
+            (None, None) => true,
+            _ => false,
+        };
+        let strokes = match (self.stroke, other.stroke) {
+            (Some((stroke, width)), Some((other, other_width))) => {
+                width == other_width && faded(stroke, other)
+            }
+            (None, None) => true,
+            _ => false,
+        };
+
+        fills && strokes
+    }
+}
+
+/// The part of the scene in view, and the transform, if any, from the scene
+/// into it. The view box is in scene units, so an unmoved camera shows the
+/// unit frame, [`UNIT_GRID_HEIGHT`](crate::camera::UNIT_GRID_HEIGHT) tall.
+fn view_box(camera: &Camera, size: (u32, u32)) -> (Rect, Option<String>) {
+    let render_size = (size.0 as f32, size.1 as f32);
+    let view_size = egui::vec2(render_size.0, render_size.1) / camera.pixels_per_unit(render_size);
+    let view_box = Rect::from_center_size(camera.position, view_size);
+
+    let rotation = (camera.rotation != 0.0).then(|| {
+        format!(
+            "rotate({} {} {})",
+            number(-camera.rotation.to_degrees()),
+            number(camera.position.x),
+            number(camera.position.y)
+        )
+    });
+
+    (view_box, rotation)
+}
+
+fn write_object(
+    svg: &mut String,
+    objects: &ObjectTree,
+    id: ObjectId,
+    parent: Transform,
+    indent: usize,
+) {
+    let Some(object) = objects.get(&id) else {
+        return;
+    };
+    let transform = parent.and_then(&object.transform);
+    let pad = "  ".repeat(indent);
+
+    match &object.object_kind {
+        ObjectKind::Model(model) => {
+            if model.path().iter().next().is_none() {
+                return;
+            }
+
+            let _ = writeln!(
+                svg,
+                r#"{pad}<path id="object-{id}" d="{}" transform="{}"{}/>"#,
+                write_path_data(model.path(), number),
+                matrix(&Affine2::from(transform)),
+                Paint::from(model).attributes(),
+            );
+        }
+        ObjectKind::Group(children) => {
+            let _ = writeln!(svg, r#"{pad}<g id="object-{id}">"#);
+            for child in children {
+                write_object(svg, objects, *child, transform, indent + 1);
+            }
+            let _ = writeln!(svg, "{pad}</g>");
+        }
+    }
+}
+
+/// Opens the `<svg>` element, with the background if it isn't transparent.
+fn svg_header(
+    svg: &mut String,
+    camera: &Camera,
+    size: (u32, u32),
+    background: Color32,
+) -> Option<String> {
+    let (view_box, rotation) = view_box(camera, size);
+
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="{} {} {} {}">"#,
+        size.0,
+        size.1,
+        number(view_box.min.x),
+        number(view_box.min.y),
+        number(view_box.width()),
+        number(view_box.height()),
+    );
+
+    if background.a() > 0 {
+        let (fill, opacity) = color(background);
+        let _ = write!(
+            svg,
+            r#"  <rect x="{}" y="{}" width="{}" height="{}" fill="{fill}""#,
+            number(view_box.min.x),
+            number(view_box.min.y),
+            number(view_box.width()),
+            number(view_box.height()),
+        );
+        if opacity != "1" {
+            let _ = write!(svg, r#" fill-opacity="{opacity}""#);
+        }
+        let _ = writeln!(svg, "/>");
+    }
+
+    rotation
+}
+
+/// Serializes a frame as an SVG document of the given size in pixels. Each
+/// model becomes a `<path>` with its transform in the scene, and each group
+/// a `<g>`, both with the id of their object.
+pub fn snapshot_to_svg(snapshot: &Snapshot, size: (u32, u32), background: Color32) -> String {
+    let mut svg = String::new();
+    let rotation = svg_header(&mut svg, &snapshot.camera, size, background);
+
+    let indent = match &rotation {
+        Some(rotation) => {
+            let _ = writeln!(svg, r#"  <g transform="{rotation}">"#);
+            2
+        }
+        None => 1,
+    };
+
+    write_object(
+        &mut svg,
+        &snapshot.objects,
+        snapshot.objects.root,
+        Transform::default(),
+        indent,
+    );
+
+    if rotation.is_some() {
+        let _ = writeln!(svg, "  </g>");
+    }
+    svg.push_str("</svg>\n");
+
+    svg
+}
+
+/// Writes the scene at `time` as an SVG, at the size and with the background
+/// of `settings`.
+pub fn write_svg(
+    scene: &Scene,
+    time: Time,
+    settings: &ExportSettings,
+    mut writer: impl Write,
+) -> Result<(), ExportError> {
+    settings.validate()?;
+
+    let size = (settings.width, settings.height);
+    let snapshot = scene.render_at(time, (size.0 as f32, size.1 as f32));
+    writer.write_all(snapshot_to_svg(&snapshot, size, settings.background).as_bytes())?;
+
+    Ok(())
+}
+
+/// What a model looks like in one frame of an animated SVG.
+#[derive(Clone, PartialEq)]
+struct Appearance {
+    data: String,
+    /// From the scene into the view box, camera included.
+    matrix: Affine2,
+    paint: Paint,
+}
+
+/// Adds the models under `id` to `models`, in drawing order.
+fn collect_models(
+    objects: &ObjectTree,
+    id: ObjectId,
+    parent: Transform,
+    view: &Affine2,
+    models: &mut Vec<(ObjectId, Appearance)>,
+) {
+    let Some(object) = objects.get(&id) else {
+        return;
+    };
+    let transform = parent.and_then(&object.transform);
+
+    match &object.object_kind {
+        ObjectKind::Model(model) => {
+            if model.path().iter().next().is_some() {
+                models.push((
+                    id,
+                    Appearance {
+                        data: write_path_data(model.path(), number),
+                        matrix: Affine2::from(transform).then(view),
+                        paint: Paint::from(model),
+                    },
+                ));
+            }
+        }
+        ObjectKind::Group(children) => {
+            for child in children {
+                collect_models(objects, *child, transform, view, models);
+            }
+        }
+    }
+}
+
+/// The transform and opacity of a model in one frame, which is all that
+/// changes for models that are animated with keyframes.
+#[derive(Clone, Copy)]
+struct Key {
+    matrix: Affine2,
+    opacity: f32,
+}
+
+impl Key {
+    fn values(&self) -> [f32; 7] {
+        let [a, b, c, d] = self.matrix.matrix;
+        let [e, f] = self.matrix.offset;
+        [a, b, c, d, e, f, self.opacity]
+    }
+
+    /// Whether `self` is where a linear animation from `from` to `to` is at
+    /// `alpha`, closely enough not to be seen. Opacities come from 8 bit
+    /// colors, so they are only compared to a few hundredths.
+    fn is_between(&self, from: &Key, to: &Key, alpha: f32) -> bool {
+        let tolerances = [1e-3, 1e-3, 1e-3, 1e-3, 1e-3, 1e-3, 1e-2];
+
+        self.values()
+            .iter()
+            .zip(from.values().iter().zip(to.values()))
+            .zip(tolerances)
+            .all(|((value, (from, to)), tolerance)| {
+                (from + (to - from) * alpha - value).abs() < tolerance
+            })
+    }
+
+    fn css(&self) -> String {
+        let [a, b, c, d, e, f, opacity] = self.values().map(number);
+        format!("transform: matrix({a},{b},{c},{d},{e},{f}); opacity: {opacity}")
+    }
+}
+
+/// The frames to keep keys for, such that animating linearly between them
+/// reproduces every frame. Frames in `keep` are kept regardless.
+fn reduce_keys(keys: &[Key], keep: &[bool]) -> Vec<usize> {
+    let last = keys.len() - 1;
+    let mut kept = vec![0];
+    let mut start = 0;
+
+    for (i, keep) in keep.iter().enumerate().take(last).skip(1) {
+        let end = i + 1;
+        let droppable = !keep
+            && (start + 1..end).all(|j| {
+                let alpha = (j - start) as f32 / (end - start) as f32;
+                keys[j].is_between(&keys[start], &keys[end], alpha)
+            });
+
+        if !droppable {
+            kept.push(i);
+            start = i;
+        }
+    }
+
+    if last > 0 {
+        kept.push(last);
+    }
+    kept
+}
+
+/// The percentage of the animation at which frame `index` of `frames` starts.
+fn percentage(index: usize, frames: usize) -> String {
+    number(index as f32 / frames as f32 * 100.0)
+}
+
+struct AnimatedSvg {
+    body: String,
+    style: String,
+    /// The CSS `animation` shorthand, without the name and timing function.
+    animation: String,
+    frames: usize,
+    next_name: usize,
+}
+
+impl AnimatedSvg {
+    fn name(&mut self) -> String {
+        self.next_name += 1;
+        format!("k{}", self.next_name)
+    }
+
+    /// A model whose path and colors stay the same, and that only moves and
+    /// fades, gets a single element with keyframes for its transform and
+    /// opacity.
+    fn add_keyframed(&mut self, id: ObjectId, frames: &[Option<Appearance>]) -> bool {
+        let Some(reference) = frames
+            .iter()
+            .flatten()
+            .max_by(|a, b| a.paint.alpha().total_cmp(&b.paint.alpha()))
+        else {
+            return true;
+        };
+        let reference_alpha = reference.paint.alpha();
+
+        let mut keys = Vec::with_capacity(frames.len());
+        for frame in frames {
+            let Some(frame) = frame else {
+                keys.push(None);
+                continue;
+            };
+
+            let opacity = if reference_alpha > 0.0 {
+                frame.paint.alpha() / reference_alpha
+            } else {
+                1.0
+            };
+            if frame.data != reference.data || !frame.paint.is_faded(&reference.paint, opacity) {
+                return false;
+            }
+
+            keys.push(Some(Key {
+                matrix: frame.matrix,
+                opacity,
+            }));
+        }
+
+        // While the model is gone, it is invisible where it last was, or
+        // where it first shows up.
+        let first = keys.iter().flatten().next().copied().unwrap();
+        let mut held = first;
+        let present: Vec<bool> = keys.iter().map(Option::is_some).collect();
+        let keys: Vec<Key> = keys
+            .into_iter()
+            .map(|key| match key {
+                Some(key) => {
+                    held = key;
+                    key
+                }
+                None => Key {
+                    opacity: 0.0,
+                    ..held
+                },
+            })
+            .collect();
+
+        let attributes = format!(
+            r#" d="{}" transform="{}"{}"#,
+            reference.data,
+            matrix(&keys[0].matrix),
+            reference.paint.attributes()
+        );
+        let opacity = |key: &Key| {
+            if key.opacity < 1.0 {
+                format!(r#" opacity="{}""#, number(key.opacity))
+            } else {
+                String::new()
+            }
+        };
+
+        if keys
+            .iter()
+            .all(|key| key.is_between(&keys[0], &keys[0], 0.0))
+        {
+            let _ = writeln!(
+                self.body,
+                r#"  <path id="object-{id}"{attributes}{}/>"#,
+                opacity(&keys[0])
+            );
+            return true;
+        }
+
+        // Appearing and disappearing happen at once rather than fading over
+        // a frame, so frames on both sides are kept, and the earlier one held
+        // until just before the later one.
+        let mut keep = vec![false; keys.len()];
+        for i in 1..keys.len() {
+            if present[i] != present[i - 1] {
+                keep[i - 1] = true;
+                keep[i] = true;
+            }
+        }
+
+        let name = self.name();
+        let _ = writeln!(
+            self.body,
+            r#"  <path id="object-{id}" class="{name}"{attributes}{}/>"#,
+            opacity(&keys[0])
+        );
+
+        let _ = writeln!(
+            self.style,
+            "    .{name} {{ animation: {name} linear {}; transform-origin: 0 0; }}",
+            self.animation
+        );
+        let _ = write!(self.style, "    @keyframes {name} {{");
+        for i in reduce_keys(&keys, &keep) {
+            if i > 0 && present[i] != present[i - 1] {
+                let _ = write!(
+                    self.style,
+                    " {}% {{ {} }}",
+                    number(i as f32 / self.frames as f32 * 100.0 - 0.001),
+                    keys[i - 1].css()
+                );
+            }
+            let _ = write!(
+                self.style,
+                " {}% {{ {} }}",
+                percentage(i, self.frames),
+                keys[i].css()
+            );
+        }
+        // The last frame lasts until the end.
+        let _ = writeln!(self.style, " 100% {{ {} }} }}", keys[keys.len() - 1].css());
+
+        true
+    }
+
+    /// Any other model gets an element for each run of frames in which it
+    /// looks the same, which is only visible during those frames.
+    fn add_per_frame(&mut self, id: ObjectId, frames: &[Option<Appearance>]) {
+        let mut start = 0;
+        while start < frames.len() {
+            let Some(appearance) = &frames[start] else {
+                start += 1;
+                continue;
+            };
+
+            let mut end = start + 1;
+            while end < frames.len() && frames[end].as_ref() == Some(appearance) {
+                end += 1;
+            }
+
+            let attributes = format!(
+                r#" d="{}" transform="{}"{}"#,
+                appearance.data,
+                matrix(&appearance.matrix),
+                appearance.paint.attributes()
+            );
+
+            if start == 0 && end == frames.len() {
+                let _ = writeln!(self.body, r#"  <path id="object-{id}"{attributes}/>"#);
+            } else {
+                let name = self.name();
+                let hidden = if start > 0 {
+                    r#" visibility="hidden""#
+                } else {
+                    ""
+                };
+                let _ = writeln!(
+                    self.body,
+                    r#"  <path id="object-{id}-{start}" class="{name}"{attributes}{hidden}/>"#
+                );
+
+                let _ = writeln!(
+                    self.style,
+                    "    .{name} {{ animation: {name} step-end {}; }}",
+                    self.animation
+                );
+                let _ = write!(self.style, "    @keyframes {name} {{");
+                if start > 0 {
+                    let _ = write!(self.style, " 0% {{ visibility: hidden }}");
+                }
+                let _ = write!(
+                    self.style,
+                    " {}% {{ visibility: visible }}",
+                    percentage(start, self.frames)
+                );
+                if end < frames.len() {
+                    let _ = write!(
+                        self.style,
+                        " {}% {{ visibility: hidden }} 100% {{ visibility: hidden }}",
+                        percentage(end, self.frames)
+                    );
+                } else {
+                    let _ = write!(self.style, " 100% {{ visibility: visible }}");
+                }
+                let _ = writeln!(self.style, " }}");
+            }
+
+            start = end;
+        }
+    }
+}
+
+/// Writes the frames as a single animated SVG that plays with CSS
+/// animations, in the view of an unmoved camera. Models that only move and
+/// fade get keyframes for their transform and opacity, with only as many
+/// keys as it takes to play like the frames. Models whose path or colors
+/// change are drawn for each frame instead, and shown only when needed, so
+/// scenes where paths change a lot make large files.
+pub fn write_animated_svg(
+    scene: &Scene,
+    settings: &ExportSettings,
+    clip: &ClipSettings,
+    mut writer: impl Write,
+) -> Result<(), ExportError> {
+    let settings = clip.frame_settings(settings)?;
+    settings.validate()?;
+
+    let size = (settings.width, settings.height);
+    let render_size = (size.0 as f32, size.1 as f32);
+    let base = Camera::default()
+        .world_to_screen(render_size)
+        .inverse()
+        .unwrap();
+
+    // The models of every frame, in the order they are drawn in, which for
+    // models that come and go is after whatever was drawn before them.
+    let times = settings.frame_times(scene);
+    let mut order: Vec<ObjectId> = Vec::new();
+    let mut tracks: HashMap<ObjectId, Vec<Option<Appearance>>> = HashMap::new();
+
+    for (frame, time) in times.iter().enumerate() {
+        let snapshot = scene.render_at(*time, render_size);
+        let view = snapshot.camera.world_to_screen(render_size).then(&base);
+
+        let mut models = Vec::new();
+        collect_models(
+            &snapshot.objects,
+            snapshot.objects.root,
+            Transform::default(),
+            &view,
+            &mut models,
+        );
+
+        let mut position = 0;
+        for (id, appearance) in models {
+            match order.iter().position(|other| *other == id) {
+                Some(existing) => position = position.max(existing + 1),
+                None => {
+                    order.insert(position, id);
+                    position += 1;
+                }
+            }
+
+            tracks.entry(id).or_insert_with(|| vec![None; times.len()])[frame] = Some(appearance);
+        }
+
+        if let Some(progress) = settings.progress {
+            progress(frame + 1, times.len());
+        }
+    }
+
+    let iterations = match clip.plays {
+        Some(plays) => plays.to_string(),
+        None => "infinite".into(),
+    };
+    let mut svg = AnimatedSvg {
+        body: String::new(),
+        style: String::new(),
+        animation: format!(
+            "{}s {iterations} both",
+            number(times.len() as f32 / settings.fps)
+        ),
+        frames: times.len(),
+        next_name: 0,
+    };
+
+    for id in order {
+        let frames = &tracks[&id];
+        if !svg.add_keyframed(id, frames) {
+            svg.add_per_frame(id, frames);
+        }
+    }
+
+    let mut document = String::new();
+    svg_header(&mut document, &Camera::default(), size, settings.background);
+    if !svg.style.is_empty() {
+        let _ = writeln!(document, "  <style>\n{}  </style>", svg.style);
+    }
+    document.push_str(&svg.body);
+    document.push_str("</svg>\n");
+
+    writer.write_all(document.as_bytes())?;
+    Ok(())
+}
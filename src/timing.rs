@@ -35,7 +35,7 @@ impl Motion for Sequence {
                 return;
             }
 
-            animation.animate(world, adusted_alpha);
+            animation.animate(world, adusted_alpha.min(1.0));
             current_alpha += normalized_alpha;
         }
     }
@@ -0,0 +1,214 @@
+use egui::{pos2, Pos2, Rect};
+use lyon::algorithms::measure::{PathMeasurements, SampleType};
+
+use crate::{
+    component::{Component, Handle},
+    dynamics::{Dynamic, OwnedDynamic},
+    object::{ObjectId, ObjectKind},
+    world::World,
+};
+
+/// The position of an object's origin in world coordinates.
+#[derive(Clone)]
+pub struct PositionOf {
+    target: ObjectId,
+}
+
+impl PositionOf {
+    pub fn new<C: Component>(handle: &Handle<C>) -> Self {
+        Self {
+            target: handle.object_id,
+        }
+    }
+}
+
+impl Dynamic<Pos2> for PositionOf {
+    fn get(&self, world: &World) -> Pos2 {
+        world.objects.world_transform(self.target).apply(Pos2::ZERO)
+    }
+}
+
+/// The bounding box of an object in world coordinates.
+#[derive(Clone)]
+pub struct BoundsOf {
+    target: ObjectId,
+}
+
+impl BoundsOf {
+    pub fn new<C: Component>(handle: &Handle<C>) -> Self {
+        Self {
+            target: handle.object_id,
+        }
+    }
+}
+
+impl Dynamic<Rect> for BoundsOf {
+    fn get(&self, world: &World) -> Rect {
+        world.objects.bounding_box(self.target)
+    }
+}
+
+/// The rotation of an object in world coordinates, in radians.
+#[derive(Clone)]
+pub struct AngleOf {
+    target: ObjectId,
+}
+
+impl AngleOf {
+    pub fn new<C: Component>(handle: &Handle<C>) -> Self {
+        Self {
+            target: handle.object_id,
+        }
+    }
+}
+
+impl Dynamic<f32> for AngleOf {
+    fn get(&self, world: &World) -> f32 {
+        world.objects.world_transform(self.target).rotation
+    }
+}
+
+/// The point a fraction `t` of the way along the path of an object, in world
+/// coordinates. For components made of several models, the first one is
+/// used.
+#[derive(Clone)]
+pub struct PointOnPath {
+    target: ObjectId,
+    t: OwnedDynamic<f32>,
+}
+
+impl PointOnPath {
+    pub fn new<C: Component>(handle: &Handle<C>, t: impl Dynamic<f32>) -> Self {
+        Self {
+            target: handle.object_id,
+            t: OwnedDynamic::new(t),
+        }
+    }
+}
+
+/// Finds the first model within the object, depth first.
+fn first_model(world: &World, id: ObjectId) -> Option<ObjectId> {
+    match &world.objects.get(&id)?.object_kind {
+        ObjectKind::Model(_) => Some(id),
+        ObjectKind::Group(children) => children.iter().find_map(|child| first_model(world, *child)),
+    }
+}
+
+impl Dynamic<Pos2> for PointOnPath {
+    fn get(&self, world: &World) -> Pos2 {
+        let Some(model_id) = first_model(world, self.target) else {
+            return PositionOf {
+                target: self.target,
+            }
+            .get(world);
+        };
+
+        let ObjectKind::Model(model) = &world.objects[&model_id].object_kind else {
+            unreachable!()
+        };
+
+        let path = model.path();
+        let measurements = PathMeasurements::from_path(path, 0.001);
+        if measurements.length() == 0.0 {
+            return world.objects.world_transform(model_id).apply(Pos2::ZERO);
+        }
+
+        let point = measurements
+            .create_sampler(path, SampleType::Normalized)
+            .sample(self.t.get(world).clamp(0.0, 1.0))
+            .position();
+
+        world
+            .objects
+            .world_transform(model_id)
+            .apply(pos2(point.x, point.y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        builder::Builder,
+        component::ComponentExt,
+        dynamics::DynamicExt,
+        group::Group,
+        motion::Motion,
+        object::{FillMaterial, Transform},
+        probe::SceneProbe,
+        scene::SceneBuilder,
+        shapes::Circle,
+    };
+    use egui::{vec2, Color32};
+
+    fn circle(radius: f32) -> Circle {
+        Circle {
+            radius,
+            material: FillMaterial::new(Color32::WHITE).into(),
+        }
+    }
+
+    #[test]
+    fn labels_follow_moving_targets() {
+        let mut b = SceneBuilder::new();
+        let dot = b.add(circle(0.1));
+        let label =
+            b.add(circle(0.2).with_position(PositionOf::new(&dot).owned() + vec2(1.0, 0.0)));
+        b.play(dot.move_to(pos2(3.0, 0.0)).with_duration(1.0));
+
+        let probe = SceneProbe::new(b.finish());
+        probe.assert_position(&label, 0.0, pos2(1.0, 0.0));
+        probe.assert_position(&label, 0.5, pos2(2.5, 0.0));
+        probe.assert_position(&label, 1.0, pos2(4.0, 0.0));
+    }
+
+    #[test]
+    fn followers_see_where_targets_are_after_motions() {
+        let mut b = SceneBuilder::new();
+        let angle = b.variable(0.0);
+        let pointer = b.add(circle(0.1).with_rotation(angle));
+        let follower = b.add(circle(0.1).with_rotation(AngleOf::new(&pointer)));
+        b.play(angle.tween_to(1.0).with_duration(1.0));
+
+        let probe = SceneProbe::new(b.finish());
+        probe.assert_rotation(&follower, 0.5, 0.5);
+        probe.assert_rotation(&follower, 1.0, 1.0);
+    }
+
+    #[test]
+    fn constant_parts_of_tracked_transforms_can_still_be_animated() {
+        let mut b = SceneBuilder::new();
+        let angle = b.variable(0.5);
+        let label = b.add(circle(0.2).with_rotation(angle));
+        b.play(label.move_to(pos2(2.0, 0.0)).with_duration(1.0));
+
+        let probe = SceneProbe::new(b.finish());
+        probe.assert_position(&label, 1.0, pos2(2.0, 0.0));
+        probe.assert_rotation(&label, 1.0, 0.5);
+    }
+
+    #[test]
+    fn trackers_inside_transformed_groups_end_up_on_their_targets() {
+        let mut b = SceneBuilder::new();
+        let dot = b.add(circle(0.1).with_rotation(0.3));
+        let group = b.add(
+            Group::from_children(vec![circle(0.2)
+                .with_position(PositionOf::new(&dot))
+                .with_rotation(AngleOf::new(&dot))])
+            .with_transform(Transform {
+                position: pos2(5.0, 1.0),
+                rotation: 0.5,
+                scale: 2.0,
+                ..Default::default()
+            }),
+        );
+        b.play(dot.move_to(pos2(2.0, -1.0)).with_duration(1.0));
+
+        let probe = SceneProbe::new(b.finish());
+        let follower = &group[0];
+        probe.assert_position(follower, 0.0, pos2(0.0, 0.0));
+        probe.assert_position(follower, 0.5, pos2(1.0, -0.5));
+        probe.assert_position(follower, 1.0, pos2(2.0, -1.0));
+        probe.assert_rotation(follower, 1.0, 0.3);
+    }
+}
@@ -2,23 +2,29 @@ use crate::{
     builder::Builder,
     component::{Component, Handle},
     group::Group,
-    object::{FillMaterial, Material, Object, ObjectId, StrokeMaterial},
-    Transform,
+    interpolation::Interpolatable,
+    motion::{set_opacity, Alpha, Motion},
+    object::{Material, Object, ObjectId, ObjectKind, Transform},
+    utils::box2d_to_rect,
+    world::World as EnimateWorldState,
 };
 use comemo::Prehashed;
-use egui::{pos2, vec2, Pos2};
+use egui::{pos2, Pos2, Rect};
 use lyon::{
     algorithms::aabb::bounding_box,
     geom::Point,
-    lyon_tessellation::{FillTessellator, StrokeTessellator},
-    path::Path,
+    path::{Event, Path},
 };
 use once_cell::sync::Lazy;
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
 use typst::{
     diag::FileResult,
     eval::Tracer,
-    foundations::{Bytes, Datetime, Smart},
-    layout::{Abs, Margin, PageElem},
+    foundations::{Bytes, Datetime},
+    layout::Abs,
     syntax::{FileId, Source, VirtualPath},
     text::{Font, FontBook},
     Library, World,
@@ -29,7 +35,7 @@ use typst_svg::svg_merged;
 const POINTS_PER_UNIT: f32 = 24.0;
 
 static LIBRARY: Lazy<Prehashed<Library>> = Lazy::new(|| {
-    let mut lib = Library::default();
+    let lib = Library::default();
     // lib.styles
     //     .set(PageElem::set_width(Smart::Custom(Abs::pt(240.0).into())));
     // lib.styles.set(PageElem::set_height(Smart::Auto));
@@ -66,7 +72,7 @@ impl World for EnimateWorld {
         Ok(self.0.clone())
     }
 
-    fn file(&self, id: FileId) -> FileResult<Bytes> {
+    fn file(&self, _id: FileId) -> FileResult<Bytes> {
         unimplemented!()
     }
 
@@ -84,12 +90,60 @@ pub struct Typst {
     pub material: Material,
 }
 
-impl Component for Typst {
-    type Handle = ();
+/// Identifies the shape of a glyph regardless of where it is placed, so the
+/// same character can be found across two different expressions.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct GlyphKey(u64);
+
+impl GlyphKey {
+    fn of(path: &Path) -> Self {
+        let mut hasher = DefaultHasher::new();
+
+        for event in path.iter() {
+            for point in [event.from(), event.to()] {
+                point.x.to_bits().hash(&mut hasher);
+                point.y.to_bits().hash(&mut hasher);
+            }
+            if let Event::Quadratic { ctrl, .. } = event {
+                ctrl.x.to_bits().hash(&mut hasher);
+                ctrl.y.to_bits().hash(&mut hasher);
+            }
+            if let Event::Cubic { ctrl1, ctrl2, .. } = event {
+                for ctrl in [ctrl1, ctrl2] {
+                    ctrl.x.to_bits().hash(&mut hasher);
+                    ctrl.y.to_bits().hash(&mut hasher);
+                }
+            }
+        }
+
+        Self(hasher.finish())
+    }
+}
 
-    fn build<B: Builder>(self, builder: &mut B) -> () {
+/// A glyph of a compiled document, with its transform in document space.
+#[derive(Clone)]
+pub struct TypstGlyph {
+    pub path: Path,
+    pub transform: Transform,
+    pub key: GlyphKey,
+}
+
+#[derive(Clone, Copy)]
+pub struct GlyphHandle {
+    pub object_id: ObjectId,
+    pub key: GlyphKey,
+}
+
+#[derive(Clone)]
+pub struct TypstHandle {
+    pub glyphs: Vec<GlyphHandle>,
+}
+
+impl Typst {
+    /// Compiles the markup into glyphs, centered around the origin.
+    pub fn compile(text: &str) -> Vec<TypstGlyph> {
         let id = FileId::new(None, VirtualPath::new("main.typ"));
-        let source = Source::new(id, self.text);
+        let source = Source::new(id, text.to_string());
         let world = EnimateWorld(source);
 
         let mut tracer = Tracer::new();
@@ -106,18 +160,12 @@ impl Component for Typst {
             }
         };
 
-        let svg = svg_merged(&document, Abs::pt(0.0).into());
+        let svg = svg_merged(&document, Abs::pt(0.0));
 
         let opt = usvg::Options::default();
         let rtree = usvg::Tree::from_data(svg.as_bytes(), &opt).unwrap();
 
-        let mut group = Group::new();
-
-        let rect = &rtree.svg_node().view_box.rect;
-        let offset = vec2(
-            (rect.x() + rect.width() / 2.0) as f32,
-            (rect.y() + rect.height() / 2.0) as f32,
-        );
+        let mut glyphs = Vec::new();
 
         for node in rtree.root().descendants() {
             if let usvg::NodeKind::Path(ref p) = *node.borrow() {
@@ -132,14 +180,314 @@ impl Component for Typst {
                 );
                 transform.scale /= POINTS_PER_UNIT;
 
-                group.add(Object::new_model(path, self.material.clone()).with_transform(transform));
+                glyphs.push(TypstGlyph {
+                    key: GlyphKey::of(&path),
+                    path,
+                    transform,
+                });
+            }
+        }
+
+        let center = Typst::bounds(&glyphs).center().to_vec2();
+        for glyph in &mut glyphs {
+            glyph.transform.position -= center;
+        }
+
+        glyphs
+    }
+
+    pub fn bounds(glyphs: &[TypstGlyph]) -> Rect {
+        glyphs.iter().fold(Rect::NOTHING, |bounds, glyph| {
+            bounds.union(
+                glyph
+                    .transform
+                    .map_aabb(box2d_to_rect(bounding_box(&glyph.path))),
+            )
+        })
+    }
+
+    /// Merges the glyphs into a single path, for when they don't need to be
+    /// animated on their own.
+    pub fn merged_path(glyphs: &[TypstGlyph]) -> Path {
+        let mut builder = Path::builder();
+
+        for glyph in glyphs {
+            for event in glyph.path.iter() {
+                builder.path_event(event.transformed(&glyph.transform));
+            }
+        }
+
+        builder.build()
+    }
+}
+
+impl Component for Typst {
+    type Handle = TypstHandle;
+
+    fn build<B: Builder>(self, builder: &mut B) -> TypstHandle {
+        CompiledTypst {
+            glyphs: Typst::compile(&self.text),
+            material: self.material,
+        }
+        .build(builder)
+    }
+}
+
+/// Glyphs that were already compiled with [`Typst::compile`], e.g., to
+/// measure them before placing them, which are added like [`Typst`] without
+/// compiling the markup again.
+pub struct CompiledTypst {
+    pub glyphs: Vec<TypstGlyph>,
+    pub material: Material,
+}
+
+impl Component for CompiledTypst {
+    type Handle = TypstHandle;
+
+    fn build<B: Builder>(self, builder: &mut B) -> TypstHandle {
+        let keys: Vec<_> = self.glyphs.iter().map(|glyph| glyph.key).collect();
+
+        let mut group = Group::new();
+
+        for glyph in self.glyphs {
+            group.add(
+                Object::new_model(glyph.path, self.material.clone())
+                    .with_transform(glyph.transform),
+            );
+        }
+
+        let group = builder.add(group);
+
+        TypstHandle {
+            glyphs: group
+                .children
+                .iter()
+                .zip(keys)
+                .map(|(glyph, key)| GlyphHandle {
+                    object_id: **glyph,
+                    key,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct GlyphMatch {
+    from: usize,
+    to: usize,
+    /// Whether the glyphs have different shapes that need to be morphed,
+    /// which is only the case for glyphs matched through a key.
+    morph: bool,
+}
+
+/// Transforms one Typst expression into another. Glyphs that appear in both
+/// expressions fly to their new positions, glyphs only in the old expression
+/// fade out, and glyphs only in the new expression fade in.
+///
+/// The new expression is added to the world by this motion, use
+/// [`TransformMatchingTypst::target`] to get a handle to it.
+pub struct TransformMatchingTypst {
+    source: ObjectId,
+    from: Vec<GlyphHandle>,
+    to: Vec<(ObjectId, TypstGlyph)>,
+    material: Material,
+    object_id: ObjectId,
+    keys: Vec<(Vec<GlyphKey>, Vec<GlyphKey>)>,
+    matches: Vec<GlyphMatch>,
+}
+
+impl Handle<Typst> {
+    pub fn transform_matching(&self, target: Typst) -> TransformMatchingTypst {
+        let to = Typst::compile(&target.text)
+            .into_iter()
+            .map(|glyph| (rand::random::<ObjectId>(), glyph))
+            .collect();
+
+        let mut transform = TransformMatchingTypst {
+            source: self.object_id,
+            from: self.glyphs.clone(),
+            to,
+            material: target.material,
+            object_id: rand::random::<ObjectId>(),
+            keys: Vec::new(),
+            matches: Vec::new(),
+        };
+        transform.update_matches();
+        transform
+    }
+}
+
+impl TransformMatchingTypst {
+    /// Matches the glyphs of `from` in the old expression with the glyphs of
+    /// `to` in the new one, even if they have different shapes (e.g., `a^2`
+    /// with `c^2`). Both are Typst markup and are matched glyph by glyph, in
+    /// order; any leftover glyphs fade.
+    pub fn with_key(mut self, from: &str, to: &str) -> Self {
+        let keys = |text| Typst::compile(text).iter().map(|glyph| glyph.key).collect();
+        self.keys.push((keys(from), keys(to)));
+        self.update_matches();
+        self
+    }
+
+    pub fn target(&self) -> Handle<Typst> {
+        Handle {
+            inner: TypstHandle {
+                glyphs: self
+                    .to
+                    .iter()
+                    .map(|(object_id, glyph)| GlyphHandle {
+                        object_id: *object_id,
+                        key: glyph.key,
+                    })
+                    .collect(),
+            },
+            object_id: self.object_id,
+        }
+    }
+
+    fn update_matches(&mut self) {
+        let from_keys: Vec<_> = self.from.iter().map(|glyph| glyph.key).collect();
+        let to_keys: Vec<_> = self.to.iter().map(|(_, glyph)| glyph.key).collect();
+
+        let mut from_taken = vec![false; from_keys.len()];
+        let mut to_taken = vec![false; to_keys.len()];
+        let mut matches = Vec::new();
+
+        for (from_run, to_run) in &self.keys {
+            let from_start = find_run(&from_keys, from_run, &from_taken);
+            let to_start = find_run(&to_keys, to_run, &to_taken);
+
+            let (Some(from_start), Some(to_start)) = (from_start, to_start) else {
+                continue;
+            };
+
+            for i in 0..from_run.len().min(to_run.len()) {
+                matches.push(GlyphMatch {
+                    from: from_start + i,
+                    to: to_start + i,
+                    morph: true,
+                });
+            }
+
+            from_taken[from_start..from_start + from_run.len()].fill(true);
+            to_taken[to_start..to_start + to_run.len()].fill(true);
+        }
+
+        for (to, key) in to_keys.iter().enumerate() {
+            if to_taken[to] {
+                continue;
+            }
+
+            let from =
+                (0..from_keys.len()).find(|&from| !from_taken[from] && from_keys[from] == *key);
+
+            if let Some(from) = from {
+                from_taken[from] = true;
+                to_taken[to] = true;
+                matches.push(GlyphMatch {
+                    from,
+                    to,
+                    morph: false,
+                });
+            }
+        }
+
+        self.matches = matches;
+    }
+}
+
+/// Finds the first place `run` appears in `keys` without overlapping any
+/// glyph that was already matched.
+fn find_run(keys: &[GlyphKey], run: &[GlyphKey], taken: &[bool]) -> Option<usize> {
+    if run.is_empty() || run.len() > keys.len() {
+        return None;
+    }
+
+    (0..=keys.len() - run.len()).find(|&start| {
+        keys[start..start + run.len()] == *run && !taken[start..start + run.len()].contains(&true)
+    })
+}
+
+impl Motion for TransformMatchingTypst {
+    fn animate(&self, world: &mut EnimateWorldState, alpha: Alpha) {
+        let alpha = alpha.clamp(0.0, 1.0);
+
+        // The new expression takes the place of the old one, and matched
+        // glyphs fly between where they are in the world on either side.
+        let placement = if world.objects.contains_key(&self.source) {
+            world.objects.world_transform(self.source)
+        } else {
+            Transform::default()
+        };
+        let to_local = placement.inverse();
+
+        let mut glyphs: Vec<_> = self
+            .to
+            .iter()
+            .map(|(_, glyph)| (glyph.path.clone(), glyph.transform, alpha))
+            .collect();
+        let mut from_matched = vec![false; self.from.len()];
+
+        for glyph_match in &self.matches {
+            let from = &self.from[glyph_match.from];
+            let to = &self.to[glyph_match.to].1;
+
+            // Glyphs that were removed since fade in like unmatched ones.
+            let Some(from_object) = world.objects.get(&from.object_id) else {
+                continue;
+            };
+
+            let path = match &from_object.object_kind {
+                ObjectKind::Model(from_model) if glyph_match.morph => {
+                    from_model.path().interpolate(&to.path, alpha)
+                }
+                _ => to.path.clone(),
+            };
+
+            let from_transform = world.objects.world_transform(from.object_id);
+            let to_transform = placement.and_then(&to.transform);
+            let transform = to_local.and_then(&from_transform.interpolate(&to_transform, alpha));
+
+            glyphs[glyph_match.to] = (path, transform, 1.0);
+            from_matched[glyph_match.from] = true;
+        }
+
+        // Once the new expression has taken its place, the old one is gone
+        // for good rather than invisible, so it doesn't count towards bounds
+        // or show up in exports.
+        if alpha >= 1.0 {
+            world.objects.remove_object(self.source);
+            for glyph in &self.from {
+                world.objects.remove_object(glyph.object_id);
+            }
+        } else {
+            for (glyph, matched) in self.from.iter().zip(from_matched) {
+                if world.objects.contains_key(&glyph.object_id) {
+                    set_opacity(
+                        world,
+                        if matched { 0.0 } else { 1.0 - alpha },
+                        glyph.object_id,
+                    );
+                }
             }
         }
 
-        builder.add(group.with_transform(Transform {
-            position: pos2(offset.x / POINTS_PER_UNIT, offset.y / POINTS_PER_UNIT),
-            ..Default::default()
-        }));
+        for ((object_id, _), (path, transform, opacity)) in self.to.iter().zip(glyphs) {
+            world.objects.add(
+                *object_id,
+                Object::new_model(path, self.material.clone()).with_transform(transform),
+                false,
+            );
+            set_opacity(world, opacity, *object_id);
+        }
+
+        world.objects.add(
+            self.object_id,
+            Object::new_group(self.to.iter().map(|(object_id, _)| *object_id).collect())
+                .with_transform(placement),
+            true,
+        );
     }
 }
 
@@ -187,3 +535,165 @@ fn convert_transform(t: &usvg::Transform) -> Transform {
         anchor: Pos2::ZERO,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        component::ComponentExt, object::FillMaterial, probe::SceneProbe, scene::SceneBuilder,
+    };
+    use egui::Color32;
+
+    fn typst(text: &str) -> Typst {
+        Typst {
+            text: text.into(),
+            material: FillMaterial::new(Color32::WHITE).into(),
+        }
+    }
+
+    fn keys(text: &str) -> Vec<GlyphKey> {
+        Typst::compile(text).iter().map(|glyph| glyph.key).collect()
+    }
+
+    #[test]
+    fn compiled_expressions_are_added_like_markup() {
+        let mut b = SceneBuilder::new();
+        let markup = b.add(typst("$x^2$"));
+        let compiled = b.add(CompiledTypst {
+            glyphs: Typst::compile("$x^2$"),
+            material: FillMaterial::new(Color32::WHITE).into(),
+        });
+        assert_eq!(markup.glyphs.len(), compiled.glyphs.len());
+
+        let probe = SceneProbe::new(b.finish());
+        let bounds = probe.object(&markup, 0.0).unwrap().bounds;
+        probe.assert_bounds(&compiled, 0.0, bounds);
+    }
+
+    #[test]
+    fn glyph_keys_match_the_same_character_anywhere() {
+        let keys = keys("$a + b + a$");
+
+        assert_eq!(keys.len(), 5);
+        assert_eq!(keys[0], keys[4]);
+        assert_eq!(keys[1], keys[3]);
+        assert_ne!(keys[0], keys[2]);
+    }
+
+    #[test]
+    fn glyph_keys_ignore_the_glyph_transform() {
+        let glyph = &Typst::compile("$x$")[0];
+        let moved = &Typst::compile("$1 + x$")[2];
+
+        assert_ne!(glyph.transform.position, moved.transform.position);
+        assert_eq!(glyph.key, moved.key);
+    }
+
+    #[test]
+    fn matching_pairs_glyphs_by_key() {
+        let mut b = SceneBuilder::new();
+        let source = b.add(typst("$a + b$"));
+
+        let matching = source.transform_matching(typst("$b + a + c$"));
+        let mut pairs: Vec<_> = matching
+            .matches
+            .iter()
+            .map(|glyph_match| (glyph_match.from, glyph_match.to, glyph_match.morph))
+            .collect();
+        pairs.sort();
+
+        assert_eq!(pairs, [(0, 2, false), (1, 1, false), (2, 0, false)]);
+    }
+
+    #[test]
+    fn matching_with_a_key_morphs_the_glyphs() {
+        let mut b = SceneBuilder::new();
+        let source = b.add(typst("$a^2$"));
+
+        let matching = source
+            .transform_matching(typst("$c^2$"))
+            .with_key("$a$", "$c$");
+        let mut pairs: Vec<_> = matching
+            .matches
+            .iter()
+            .map(|glyph_match| (glyph_match.from, glyph_match.to, glyph_match.morph))
+            .collect();
+        pairs.sort();
+
+        assert_eq!(pairs, [(0, 0, true), (1, 1, false)]);
+    }
+
+    #[test]
+    fn matching_takes_the_place_of_the_source() {
+        let mut b = SceneBuilder::new();
+        let source: Handle<Typst> = b
+            .add(
+                typst("$a + b$")
+                    .with_position(pos2(3.0, 1.0))
+                    .with_scale(2.0),
+            )
+            .into();
+        let matching = source.transform_matching(typst("$a + b$"));
+        let target = matching.target();
+        b.play(matching.with_duration(1.0));
+
+        let probe = SceneProbe::new(b.finish());
+        probe.assert_position(&target, 0.5, pos2(3.0, 1.0));
+        probe.assert_position(&target, 1.0, pos2(3.0, 1.0));
+
+        // Identical expressions don't move at all along the way.
+        let halfway = probe.at(0.5);
+        for (from, to) in source.glyphs.iter().zip(&target.glyphs) {
+            let from = halfway.get_by_id(from.object_id).unwrap();
+            let to = halfway.get_by_id(to.object_id).unwrap();
+            assert!(from.position.distance(to.position) < 1e-4);
+            assert!((from.transform.scale - to.transform.scale).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn matching_removes_the_source_once_done() {
+        let mut b = SceneBuilder::new();
+        let source = b.add(typst("$a + b$"));
+        let matching = source.transform_matching(typst("$b + c$"));
+        let target = matching.target();
+        b.play(matching.with_duration(1.0));
+
+        let probe = SceneProbe::new(b.finish());
+        probe.assert_present(&source, 0.5, true);
+        probe.assert_present(&source, 1.0, false);
+        probe.assert_present(&target, 1.0, true);
+
+        let done = probe.at(1.0);
+        for glyph in &source.glyphs {
+            assert!(done.get_by_id(glyph.object_id).is_none());
+        }
+        let root = &done.snapshot.objects[&done.snapshot.objects.root];
+        let ObjectKind::Group(rooted) = &root.object_kind else {
+            unreachable!()
+        };
+        assert!(!rooted.contains(&source.object_id));
+    }
+
+    #[test]
+    fn matching_keeps_the_alpha_of_the_target() {
+        let mut b = SceneBuilder::new();
+        let source = b.add(typst("$a + b$"));
+        let matching = source.transform_matching(Typst {
+            text: "$a + c$".into(),
+            material: FillMaterial::new(Color32::from_rgba_unmultiplied(255, 255, 255, 128)).into(),
+        });
+        let target = matching.target();
+        b.play(matching.with_duration(1.0));
+
+        let probe = SceneProbe::new(b.finish());
+        probe.assert_opacity(&target, 1.0, 128.0 / 255.0);
+        for glyph in &target.glyphs {
+            let glyph = probe.at(1.0).get_by_id(glyph.object_id).unwrap();
+            assert_eq!(
+                glyph.material().unwrap().fill.as_ref().unwrap().color.a(),
+                128
+            );
+        }
+    }
+}
@@ -1,42 +1,237 @@
-use crate::object_tree::ObjectTree;
-use std::collections::HashMap;
+use crate::{camera::Camera, object_tree::ObjectTree, timing::Time};
+use egui::{Color32, Pos2, Rect};
+use std::{collections::HashMap, marker::PhantomData, rc::Rc};
 
 pub type Variable = usize;
 
+pub type Updater = Rc<dyn Fn(&mut World)>;
+
+/// The value of a variable, whatever its type.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VariableValue {
+    F32(f32),
+    Pos2(Pos2),
+    Color(Color32),
+    Bool(bool),
+}
+
+/// A type that can be stored in a variable.
+pub trait VariableType: Copy + 'static {
+    fn into_value(self) -> VariableValue;
+    fn from_value(value: VariableValue) -> Option<Self>;
+}
+
+macro_rules! variable_type {
+    ($type:ty, $variant:ident) => {
+        impl VariableType for $type {
+            fn into_value(self) -> VariableValue {
+                VariableValue::$variant(self)
+            }
+
+            fn from_value(value: VariableValue) -> Option<Self> {
+                match value {
+                    VariableValue::$variant(value) => Some(value),
+                    _ => None,
+                }
+            }
+        }
+
+        impl From<$type> for VariableValue {
+            fn from(value: $type) -> Self {
+                value.into_value()
+            }
+        }
+    };
+}
+
+variable_type!(f32, F32);
+variable_type!(Pos2, Pos2);
+variable_type!(Color32, Color);
+variable_type!(bool, Bool);
+
+/// A typed handle to a variable. Until a motion or an input sets it, the
+/// variable has its initial value.
+pub struct Var<T: VariableType> {
+    id: Variable,
+    initial: T,
+    _type: PhantomData<T>,
+}
+
+impl<T: VariableType> Clone for Var<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: VariableType> Copy for Var<T> {}
+
+impl<T: VariableType> Var<T> {
+    /// A variable with a fixed id, for inputs that are supplied from outside
+    /// the scene.
+    pub const fn input(id: Variable, initial: T) -> Self {
+        Self {
+            id,
+            initial,
+            _type: PhantomData,
+        }
+    }
+
+    pub fn new(initial: T) -> Self {
+        Self::input(rand::random(), initial)
+    }
+
+    pub fn id(&self) -> Variable {
+        self.id
+    }
+
+    pub fn initial(&self) -> T {
+        self.initial
+    }
+}
+
+/// The position of the mouse in scene coordinates, supplied by the player.
+pub const MOUSE_POSITION: Var<Pos2> = Var::input(0, Pos2::ZERO);
+
 pub struct World {
     pub objects: ObjectTree,
+    pub camera: Camera,
     render_size: (f32, f32),
-    variables: HashMap<Variable, f32>,
+    variables: HashMap<Variable, VariableValue>,
+    updaters: Vec<Updater>,
+    time: Time,
+    absolute_time: Time,
 }
 
 impl World {
     pub fn new(
         objects: ObjectTree,
         render_size: (f32, f32),
-        variables: HashMap<Variable, f32>,
+        variables: HashMap<Variable, VariableValue>,
     ) -> Self {
         Self {
             objects,
+            camera: Camera::default(),
             render_size,
             variables,
+            updaters: Vec::new(),
+            time: 0.0,
+            absolute_time: 0.0,
         }
     }
 
+    /// Sets the time of the scene being rendered, and of the outermost scene
+    /// if the scene is embedded in another one.
+    pub fn with_time(mut self, time: Time, absolute_time: Time) -> Self {
+        self.time = time;
+        self.absolute_time = absolute_time;
+        self
+    }
+
+    /// The time within the scene being rendered, which for an embedded scene
+    /// starts when the scene does.
+    pub fn time(&self) -> Time {
+        self.time
+    }
+
+    /// The time within the outermost scene.
+    pub fn absolute_time(&self) -> Time {
+        self.absolute_time
+    }
+
     pub fn render_size(&self) -> (f32, f32) {
         self.render_size
     }
 
-    pub fn update_variable(&mut self, variable: Variable, value: f32) {
-        self.variables.insert(variable, value);
+    /// The part of the scene the camera shows, in scene coordinates.
+    pub fn frame(&self) -> Rect {
+        self.camera.visible_rect(self.render_size)
+    }
+
+    pub fn variables(&self) -> &HashMap<Variable, VariableValue> {
+        &self.variables
+    }
+
+    pub fn set_variable<T: VariableType>(&mut self, variable: Var<T>, value: T) {
+        self.variables.insert(variable.id, value.into_value());
+    }
+
+    /// Sets a variable by id, e.g., an input forwarded from outside the scene.
+    pub fn update_variable(&mut self, variable: Variable, value: impl Into<VariableValue>) {
+        self.variables.insert(variable, value.into());
     }
 
-    pub fn update_variables(&mut self, variables: &HashMap<Variable, f32>) {
+    pub fn update_variables(&mut self, variables: &HashMap<Variable, VariableValue>) {
         for (variable, value) in variables {
             self.update_variable(*variable, *value);
         }
     }
 
-    pub fn get_variable(&self, variable: Variable) -> f32 {
-        *self.variables.get(&variable).expect("Variable not found")
+    /// The current value of the variable, or its initial value if it hasn't
+    /// been set. Setting it to a value of another type is a bug, which is
+    /// caught in debug builds and otherwise also gives the initial value.
+    pub fn get_variable<T: VariableType>(&self, variable: Var<T>) -> T {
+        let Some(value) = self.variables.get(&variable.id) else {
+            return variable.initial;
+        };
+
+        let typed = T::from_value(*value);
+        debug_assert!(
+            typed.is_some(),
+            "variable {} holds {value:?}, which is not a {}",
+            variable.id,
+            std::any::type_name::<T>()
+        );
+
+        typed.unwrap_or(variable.initial)
+    }
+
+    /// Registers an updater that runs once every motion of the frame has been
+    /// applied, so it sees the final state of the objects it depends on.
+    pub fn add_updater(&mut self, updater: Updater) {
+        self.updaters.push(updater);
+    }
+
+    pub fn run_updaters(&mut self) {
+        for updater in std::mem::take(&mut self.updaters) {
+            updater(self);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn world() -> World {
+        World::new(ObjectTree::new(), (1920.0, 1080.0), HashMap::new())
+    }
+
+    #[test]
+    fn unset_variables_have_their_initial_value() {
+        let world = world();
+        assert_eq!(world.get_variable(Var::new(2.5)), 2.5);
+    }
+
+    #[test]
+    fn variables_can_be_updated_by_id() {
+        let mut world = world();
+        let variable = Var::new(0.0);
+
+        world.update_variable(variable.id(), 1.0);
+        assert_eq!(world.get_variable(variable), 1.0);
+
+        world.update_variables(&HashMap::from([(variable.id(), VariableValue::F32(2.0))]));
+        assert_eq!(world.get_variable(variable), 2.0);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "which is not a f32")]
+    fn reading_a_variable_as_another_type_is_caught() {
+        let mut world = world();
+        let variable = Var::new(0.0);
+
+        world.update_variable(variable.id(), Pos2::ZERO);
+        world.get_variable(variable);
     }
 }
@@ -0,0 +1,166 @@
+//! Renders small scenes on the CPU and compares them against the references
+//! in `tests/golden`. Run with `ENIMATE_BLESS=1` to update the references
+//! after a change that is meant to change how things look.
+
+use egui::{pos2, vec2, Color32};
+use enimate::{
+    builder::Builder,
+    component::ComponentExt,
+    easing::Easing,
+    golden::Golden,
+    group::Group,
+    motion::Motion,
+    object::{FillMaterial, Material, StrokeMaterial, Transform},
+    scene::SceneBuilder,
+    shapes::{Circle, RegularPolygon, RoundedRect, Star},
+    typst::Typst,
+};
+
+fn golden() -> Golden {
+    Golden::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden"))
+}
+
+/// Fills and strokes of curved and straight paths, which go through
+/// tessellation.
+#[test]
+fn shapes() {
+    let mut b = SceneBuilder::new();
+
+    b.add(
+        Circle {
+            radius: 1.0,
+            material: FillMaterial::new(Color32::RED).into(),
+        }
+        .with_position(pos2(-4.5, 0.0)),
+    );
+    b.add(
+        Star {
+            points: 5,
+            outer_radius: 1.2,
+            inner_radius: 0.5,
+            material: Material {
+                fill: Some(FillMaterial::new(Color32::GOLD)),
+                stroke: Some(StrokeMaterial::new(Color32::WHITE, 0.1)),
+            },
+        }
+        .with_position(pos2(-1.5, 0.0)),
+    );
+    b.add(
+        RoundedRect {
+            size: vec2(2.0, 1.5),
+            corner_radius: 0.3,
+            material: StrokeMaterial::new(Color32::LIGHT_BLUE, 0.15).into(),
+        }
+        .with_position(pos2(1.5, 0.0)),
+    );
+    b.add(
+        RegularPolygon {
+            sides: 6,
+            radius: 1.0,
+            material: FillMaterial::new(Color32::from_rgba_unmultiplied(0, 255, 0, 128)).into(),
+        }
+        .with_position(pos2(4.5, 0.0)),
+    );
+
+    golden().assert_matches("shapes", &b.finish(), 0.0);
+}
+
+/// Children of a moved, turned and scaled group, which have transforms of
+/// their own.
+#[test]
+fn nested_transforms() {
+    let mut b = SceneBuilder::new();
+
+    let mut group = Group::new();
+    for i in 0..3 {
+        group.add(
+            RoundedRect {
+                size: vec2(1.0, 0.5),
+                corner_radius: 0.1,
+                material: FillMaterial::new(Color32::from_rgb(80 * i as u8 + 60, 120, 200)).into(),
+            }
+            .with_position(pos2(i as f32 * 1.5 - 1.5, 0.0))
+            .with_rotation(i as f32 * 0.4),
+        );
+    }
+    b.add(
+        group.with_transform(
+            Transform::default()
+                .with_position(pos2(1.0, -0.5))
+                .with_rotation(0.3)
+                .with_scale(1.5),
+        ),
+    );
+
+    golden().assert_matches("nested_transforms", &b.finish(), 0.0);
+}
+
+/// Halfway through moving and fading out.
+#[test]
+fn animation() {
+    let mut b = SceneBuilder::new();
+
+    let circle = b.add(Circle {
+        radius: 1.0,
+        material: FillMaterial::new(Color32::WHITE).into(),
+    });
+    let square = b.add(RegularPolygon {
+        sides: 4,
+        radius: 1.0,
+        material: FillMaterial::new(Color32::RED).into(),
+    });
+    b.play(circle.move_to(pos2(3.0, 1.0)).with_duration(1.0));
+    b.play(
+        square
+            .fade_out()
+            .with_duration(1.0)
+            .with_easing(Easing::EaseInOut),
+    );
+
+    let scene = b.finish();
+    golden().assert_matches("animation_move", &scene, 0.5);
+    golden().assert_matches("animation_fade", &scene, 1.5);
+}
+
+/// A zoomed in and turned camera.
+#[test]
+fn camera() {
+    let mut b = SceneBuilder::new();
+
+    b.add(
+        Star {
+            points: 6,
+            outer_radius: 1.0,
+            inner_radius: 0.6,
+            material: FillMaterial::new(Color32::KHAKI).into(),
+        }
+        .with_position(pos2(2.0, 1.0)),
+    );
+    b.add(Circle {
+        radius: 0.5,
+        material: FillMaterial::new(Color32::LIGHT_RED).into(),
+    });
+
+    let camera = b.camera();
+    b.play(camera.move_to(pos2(1.0, 0.5)).with_duration(1.0));
+    b.play(camera.zoom_to(2.0).with_duration(1.0));
+    b.play(camera.rotate_to(0.4).with_duration(1.0));
+
+    golden().assert_matches("camera", &b.finish(), 3.0);
+}
+
+/// Glyphs of typeset math.
+#[test]
+fn typst() {
+    let mut b = SceneBuilder::new();
+
+    b.add(
+        Typst {
+            text: "$e^(i pi) + 1 = 0$".into(),
+            material: FillMaterial::new(Color32::WHITE).into(),
+        }
+        .with_scale(3.0),
+    );
+
+    golden().assert_matches("typst", &b.finish(), 0.0);
+}